@@ -0,0 +1,70 @@
+extern crate osprey;
+
+use osprey::perft::perft;
+use osprey::Board;
+use std::time::Instant;
+
+///
+/// The perft positions of `tests/perft_test.rs` with the depth to benchmark them at.
+///
+const POSITIONS: [(&str, &str, u8); 6] = [
+    (
+        "start",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        5,
+    ),
+    (
+        "kiwipete",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        4,
+    ),
+    ("position_3", "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", 5),
+    (
+        "position_4",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        4,
+    ),
+    (
+        "position_5",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        4,
+    ),
+    (
+        "position_6",
+        "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+        4,
+    ),
+];
+
+fn main() {
+    let mut total_nodes = 0;
+    let mut total_seconds = 0.0;
+
+    for (name, fen, depth) in POSITIONS {
+        let board = Board::new(fen);
+
+        let start = Instant::now();
+        let nodes = perft(&board, 0, depth, 1);
+        let seconds = start.elapsed().as_secs_f64();
+
+        total_nodes += nodes;
+        total_seconds += seconds;
+
+        println!(
+            "{:<12} depth {} {:>10} nodes {:>8.3} s {:>12.0} nodes/s",
+            name,
+            depth,
+            nodes,
+            seconds,
+            nodes as f64 / seconds
+        );
+    }
+
+    println!(
+        "{:<12}         {:>10} nodes {:>8.3} s {:>12.0} nodes/s",
+        "total",
+        total_nodes,
+        total_seconds,
+        total_nodes as f64 / total_seconds
+    );
+}
@@ -0,0 +1,367 @@
+use crate::square::Square;
+use std::fmt;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Shl, Shr};
+
+const FILE_A: u64 = 0x0101_0101_0101_0101;
+const FILE_H: u64 = 0x8080_8080_8080_8080;
+
+///
+/// A direction on the board, from the point of view of white.
+///
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    ///
+    /// All directions, clockwise from north.
+    ///
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    ///
+    /// Get the change in square index of a step in the direction.
+    ///
+    pub const fn offset(self) -> i8 {
+        match self {
+            Direction::North => 8,
+            Direction::NorthEast => 9,
+            Direction::East => 1,
+            Direction::SouthEast => -7,
+            Direction::South => -8,
+            Direction::SouthWest => -9,
+            Direction::West => -1,
+            Direction::NorthWest => 7,
+        }
+    }
+}
+
+///
+/// A set of squares, stored as one bit per square with bit 0 for a1 and bit 63 for h8.
+///
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Default)]
+pub struct Bitboard(pub u64);
+
+impl Bitboard {
+    ///
+    /// The bitboard without any squares.
+    ///
+    pub const EMPTY: Bitboard = Bitboard(0);
+
+    ///
+    /// The bitboard with all squares.
+    ///
+    pub const FULL: Bitboard = Bitboard(!0);
+
+    ///
+    /// Get the number of squares in the bitboard.
+    ///
+    #[inline]
+    pub const fn count(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    ///
+    /// Whether the bitboard has no squares.
+    ///
+    #[inline]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    ///
+    /// Whether the bitboard contains a square.
+    ///
+    /// # Arguments
+    ///
+    /// * `square` - The square to look for.
+    ///
+    #[inline]
+    pub const fn contains(self, square: Square) -> bool {
+        self.0 & 1u64 << square.index() != 0
+    }
+
+    ///
+    /// Get the square with the lowest index in the bitboard.
+    ///
+    /// # Returns
+    ///
+    /// The square, or `None` if the bitboard is empty.
+    ///
+    #[inline]
+    pub const fn first(self) -> Option<Square> {
+        if self.0 == 0 {
+            None
+        } else {
+            Some(Square::new(self.0.trailing_zeros() as u8))
+        }
+    }
+
+    ///
+    /// Shift every square of the bitboard one step in a direction.
+    ///
+    /// Squares shifted off the board are dropped, they do not wrap around to the other edge.
+    ///
+    /// # Arguments
+    ///
+    /// * `direction` - The direction to shift the squares in.
+    ///
+    pub const fn shift(self, direction: Direction) -> Bitboard {
+        let bits = match direction {
+            Direction::North => self.0 << 8,
+            Direction::NorthEast => (self.0 & !FILE_H) << 9,
+            Direction::East => (self.0 & !FILE_H) << 1,
+            Direction::SouthEast => (self.0 & !FILE_H) >> 7,
+            Direction::South => self.0 >> 8,
+            Direction::SouthWest => (self.0 & !FILE_A) >> 9,
+            Direction::West => (self.0 & !FILE_A) >> 1,
+            Direction::NorthWest => (self.0 & !FILE_A) << 7,
+        };
+
+        Bitboard(bits)
+    }
+}
+
+impl From<u64> for Bitboard {
+    fn from(bits: u64) -> Bitboard {
+        Bitboard(bits)
+    }
+}
+
+impl From<Bitboard> for u64 {
+    fn from(bitboard: Bitboard) -> u64 {
+        bitboard.0
+    }
+}
+
+impl From<Square> for Bitboard {
+    fn from(square: Square) -> Bitboard {
+        square.bitboard()
+    }
+}
+
+impl FromIterator<Square> for Bitboard {
+    fn from_iter<I: IntoIterator<Item = Square>>(squares: I) -> Bitboard {
+        squares
+            .into_iter()
+            .fold(Bitboard::EMPTY, |bitboard, square| bitboard | square)
+    }
+}
+
+impl Not for Bitboard {
+    type Output = Bitboard;
+
+    fn not(self) -> Bitboard {
+        Bitboard(!self.0)
+    }
+}
+
+impl Shl<u32> for Bitboard {
+    type Output = Bitboard;
+
+    ///
+    /// Shifts the bits towards h8, giving an empty bitboard when shifting by 64 or more.
+    ///
+    fn shl(self, amount: u32) -> Bitboard {
+        Bitboard(self.0.checked_shl(amount).unwrap_or(0))
+    }
+}
+
+impl Shr<u32> for Bitboard {
+    type Output = Bitboard;
+
+    ///
+    /// Shifts the bits towards a1, giving an empty bitboard when shifting by 64 or more.
+    ///
+    fn shr(self, amount: u32) -> Bitboard {
+        Bitboard(self.0.checked_shr(amount).unwrap_or(0))
+    }
+}
+
+///
+/// Implement a binary bit operator and its assigning variant for bitboards, with either another
+/// bitboard or a square as the right-hand side.
+///
+macro_rules! bit_operator {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $op:tt) => {
+        impl $trait for Bitboard {
+            type Output = Bitboard;
+
+            fn $method(self, other: Bitboard) -> Bitboard {
+                Bitboard(self.0 $op other.0)
+            }
+        }
+
+        impl $trait<Square> for Bitboard {
+            type Output = Bitboard;
+
+            fn $method(self, other: Square) -> Bitboard {
+                Bitboard(self.0 $op other.bitboard().0)
+            }
+        }
+
+        impl $assign_trait for Bitboard {
+            fn $assign_method(&mut self, other: Bitboard) {
+                self.0 = self.0 $op other.0;
+            }
+        }
+
+        impl $assign_trait<Square> for Bitboard {
+            fn $assign_method(&mut self, other: Square) {
+                self.0 = self.0 $op other.bitboard().0;
+            }
+        }
+    };
+}
+
+bit_operator!(BitAnd, bitand, BitAndAssign, bitand_assign, &);
+bit_operator!(BitOr, bitor, BitOrAssign, bitor_assign, |);
+bit_operator!(BitXor, bitxor, BitXorAssign, bitxor_assign, ^);
+
+///
+/// An iterator over the squares of a bitboard, from a1 to h8.
+///
+pub struct Squares(u64);
+
+impl Iterator for Squares {
+    type Item = Square;
+
+    #[inline]
+    fn next(&mut self) -> Option<Square> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        let square = Square::new(self.0.trailing_zeros() as u8);
+        self.0 &= self.0 - 1;
+        Some(square)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.0.count_ones() as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for Squares {}
+
+impl IntoIterator for Bitboard {
+    type Item = Square;
+    type IntoIter = Squares;
+
+    fn into_iter(self) -> Squares {
+        Squares(self.0)
+    }
+}
+
+///
+/// Draws the bitboard as a grid with the eighth rank at the top, marking the squares in the
+/// bitboard with `x` and the others with `.`.
+///
+impl fmt::Debug for Bitboard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for rank in (0..8).rev() {
+            for file in 0..8 {
+                if self.contains(Square::new(rank * 8 + file)) {
+                    write!(f, "x ")?;
+                } else {
+                    write!(f, ". ")?;
+                }
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn bitboard_operators() {
+    let bitboard = Bitboard::EMPTY | Square::E4 | Square::D5;
+
+    assert_eq!(bitboard.count(), 2);
+    assert!(bitboard.contains(Square::E4));
+    assert!(!bitboard.contains(Square::E5));
+    assert_eq!(bitboard & Square::E4.bitboard(), Bitboard::from(Square::E4));
+    assert_eq!(bitboard ^ Square::E4, Bitboard::from(Square::D5));
+    assert_eq!((!bitboard).count(), 62);
+    assert_eq!(bitboard.first(), Some(Square::E4));
+    assert_eq!(
+        bitboard.into_iter().collect::<Vec<Square>>(),
+        vec![Square::E4, Square::D5]
+    );
+    assert_eq!(bitboard.into_iter().collect::<Bitboard>(), bitboard);
+}
+
+#[test]
+fn bitboard_shift() {
+    let corners = Bitboard::EMPTY | Square::A1 | Square::H1 | Square::A8 | Square::H8;
+
+    assert_eq!(
+        corners.shift(Direction::North),
+        Bitboard::EMPTY | Square::A2 | Square::H2
+    );
+    assert_eq!(
+        corners.shift(Direction::East),
+        Bitboard::EMPTY | Square::B1 | Square::B8
+    );
+    assert_eq!(
+        corners.shift(Direction::SouthWest),
+        Bitboard::from(Square::G7)
+    );
+    assert_eq!(
+        corners.shift(Direction::NorthWest),
+        Bitboard::from(Square::G2)
+    );
+
+    // a step off the board drops one rank or file, a diagonal step drops both
+    for direction in Direction::ALL {
+        let expected = match direction.offset().abs() {
+            1 | 8 => 56,
+            _ => 49,
+        };
+
+        assert_eq!(Bitboard::FULL.shift(direction).count(), expected);
+    }
+
+    // shifting the bits out of the board empties it, however far
+    assert_eq!(corners << 8, Bitboard::EMPTY | Square::A2 | Square::H2);
+    assert_eq!(corners >> 63, Bitboard::from(Square::A1));
+    for amount in [64, 65, u32::MAX] {
+        assert_eq!(Bitboard::FULL << amount, Bitboard::EMPTY);
+        assert_eq!(Bitboard::FULL >> amount, Bitboard::EMPTY);
+    }
+}
+
+#[test]
+fn bitboard_debug() {
+    let bitboard = Bitboard::EMPTY | Square::A1 | Square::H8;
+
+    assert_eq!(
+        format!("{:?}", bitboard),
+        ". . . . . . . x \n\
+         . . . . . . . . \n\
+         . . . . . . . . \n\
+         . . . . . . . . \n\
+         . . . . . . . . \n\
+         . . . . . . . . \n\
+         . . . . . . . . \n\
+         x . . . . . . . \n"
+    );
+}
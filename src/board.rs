@@ -1,80 +1,175 @@
+use crate::bitboard::Bitboard;
+use crate::builder::CastlingRights;
+use crate::color::Color;
+use crate::fen::{FenError, FenErrorKind, FenField};
+use crate::magic;
+use crate::movelist::MoveList;
+use crate::notation::MoveParseError;
+use crate::outcome::{Outcome, Status, Termination};
+use crate::square::{File, Rank, Square};
+use crate::validation::{PositionError, PositionIssue};
+use crate::zobrist;
 use std::fmt;
+use std::str::FromStr;
+
+///
+/// The FEN string of the starting position.
+///
+pub const STARTING_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
 
 const FILE_A: u64 = 72340172838076673;
-const FILE_B: u64 = 144680345676153346;
-const FILE_C: u64 = 289360691352306692;
-const FILE_D: u64 = 578721382704613384;
-const FILE_E: u64 = 1157442765409226768;
-const FILE_F: u64 = 2314885530818453536;
-const FILE_G: u64 = 4629771061636907072;
 const FILE_H: u64 = 9259542123273814144;
 const RANK_1: u64 = 255;
-const RANK_2: u64 = 65280;
 const RANK_3: u64 = 16711680;
-const RANK_4: u64 = 4278190080;
-const RANK_5: u64 = 1095216660480;
 const RANK_6: u64 = 280375465082880;
-const RANK_7: u64 = 71776119061217280;
 const RANK_8: u64 = 18374686479671623680;
+const DARK_SQUARES: u64 = 12273903644374837845;
+
+const KNIGHT_OFFSETS: [(i8, i8); 8] = [
+    (2, 1),
+    (2, -1),
+    (1, 2),
+    (1, -2),
+    (-1, 2),
+    (-1, -2),
+    (-2, 1),
+    (-2, -1),
+];
+const KING_OFFSETS: [(i8, i8); 8] = [
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, 1),
+    (0, -1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+///
+/// Calculates the positions a leaper attacks from every position.
+///
+/// # Arguments
+///
+/// * `offsets` - The (rank, file) offsets of the attacked positions.
+///
+/// # Returns
+///
+/// An array of bitboards representing the attacked positions, indexed by the position of the
+/// leaper.
+///
+const fn leaper_attacks(offsets: &[(i8, i8)]) -> [u64; 64] {
+    let mut attacks = [0u64; 64];
+    let mut position = 0;
 
-// Left here for later use
-// const CENTER: u64 = 103481868288;
-// const EXTENDED_CENTER: u64 = 66229406269440;
-// const KING_SIDE: u64 = 9295429630892703744;
-// const QUEEN_SIDE: u64 = 4755801206503243840;
-// const WHITE_SQUARES: u64 = 2863311530;
-// const BLACK_SQUARES: u64 = 1431655765;
+    while position < 64 {
+        let mut i = 0;
 
-const KNIGHT_SPAN: u64 = 43234889994;
-const KING_SPAN: u64 = 460039;
+        while i < offsets.len() {
+            let rank = (position / 8) as i8 + offsets[i].0;
+            let file = (position % 8) as i8 + offsets[i].1;
 
-const RANKS: [u64; 8] = [
-    RANK_1, RANK_2, RANK_3, RANK_4, RANK_5, RANK_6, RANK_7, RANK_8,
-];
-const FILES: [u64; 8] = [
-    FILE_A, FILE_B, FILE_C, FILE_D, FILE_E, FILE_F, FILE_G, FILE_H,
-];
-const DIAGONALS: [u64; 15] = [
-    0x1,
-    0x102,
-    0x10204,
-    0x1020408,
-    0x102040810,
-    0x10204081020,
-    0x1020408102040,
-    0x102040810204080,
-    0x204081020408000,
-    0x408102040800000,
-    0x810204080000000,
-    0x1020408000000000,
-    0x2040800000000000,
-    0x4080000000000000,
-    0x8000000000000000,
-];
+            if rank >= 0 && rank < 8 && file >= 0 && file < 8 {
+                attacks[position] |= 1u64 << (rank * 8 + file);
+            }
 
-const ANTI_DIAGONALS: [u64; 15] = [
-    0x80,
-    0x8040,
-    0x804020,
-    0x80402010,
-    0x8040201008,
-    0x804020100804,
-    0x80402010080402,
-    0x8040201008040201,
-    0x4020100804020100,
-    0x2010080402010000,
-    0x1008040201000000,
-    0x804020100000000,
-    0x402010000000000,
-    0x201000000000000,
-    0x100000000000000,
+            i += 1;
+        }
+
+        position += 1;
+    }
+
+    attacks
+}
+
+///
+/// The positions attacked by a knight, indexed by its position.
+///
+const KNIGHT_ATTACKS: [u64; 64] = leaper_attacks(&KNIGHT_OFFSETS);
+
+///
+/// The positions attacked by a king, indexed by its position.
+///
+const KING_ATTACKS: [u64; 64] = leaper_attacks(&KING_OFFSETS);
+
+///
+/// The positions attacked by a pawn, indexed by its color (0 for white, 1 for black) and its
+/// position.
+///
+const PAWN_ATTACKS: [[u64; 64]; 2] = [
+    leaper_attacks(&[(1, -1), (1, 1)]),
+    leaper_attacks(&[(-1, -1), (-1, 1)]),
 ];
 
+///
+/// The values of the pieces in centipawns as used by the static exchange evaluation, indexed by
+/// the order of `Piece::ALL` modulo 6 (pawn, knight, bishop, rook, queen and king). The king is
+/// worth more than all other pieces together, so no exchange ends with a king capture.
+///
+const SEE_VALUES: [i32; 6] = [100, 300, 300, 500, 900, 20000];
+
+///
+/// An iterator over the positions of the set bits of a bitboard, from a1 to h8.
+///
+struct SetBits(u64);
+
+impl Iterator for SetBits {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        let position = self.0.trailing_zeros() as u8;
+        self.0 &= self.0 - 1;
+
+        Some(position)
+    }
+}
+
+///
+/// Shifts a bitboard towards the eighth rank for positive offsets and towards the first rank
+/// for negative offsets.
+///
+#[inline]
+fn shift(bitboard: u64, offset: i8) -> u64 {
+    if offset >= 0 {
+        bitboard << offset
+    } else {
+        bitboard >> -offset
+    }
+}
+
+///
+/// The bitboards of the pieces of one side.
+///
+#[derive(Debug, Copy, Clone)]
+struct Side {
+    pawns: u64,
+    knights: u64,
+    bishops: u64,
+    rooks: u64,
+    queens: u64,
+    king: u64,
+    /// All pieces except the king.
+    pieces: u64,
+}
+
+impl Side {
+    ///
+    /// Get all pieces of the side, including the king.
+    ///
+    fn all(&self) -> u64 {
+        self.pieces | self.king
+    }
+}
+
 ///
 /// The type of a piece.
 /// 
 #[allow(dead_code)]
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum Piece {
     WhitePawn,
     WhiteKnight,
@@ -90,49 +185,268 @@ pub enum Piece {
     BlackKing,
 }
 
+impl Piece {
+    ///
+    /// All pieces in the order of their bitboards.
+    ///
+    pub(crate) const ALL: [Piece; 12] = [
+        Piece::WhitePawn,
+        Piece::WhiteKnight,
+        Piece::WhiteBishop,
+        Piece::WhiteRook,
+        Piece::WhiteQueen,
+        Piece::WhiteKing,
+        Piece::BlackPawn,
+        Piece::BlackKnight,
+        Piece::BlackBishop,
+        Piece::BlackRook,
+        Piece::BlackQueen,
+        Piece::BlackKing,
+    ];
+
+    ///
+    /// Get the color of the piece.
+    ///
+    pub const fn color(self) -> Color {
+        match self {
+            Piece::WhitePawn
+            | Piece::WhiteKnight
+            | Piece::WhiteBishop
+            | Piece::WhiteRook
+            | Piece::WhiteQueen
+            | Piece::WhiteKing => Color::White,
+            _ => Color::Black,
+        }
+    }
+
+    ///
+    /// Get the piece of a color and kind.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The color of the piece.
+    /// * `kind` - The kind of the piece.
+    ///
+    pub const fn new(color: Color, kind: PieceKind) -> Piece {
+        Piece::ALL[if color.is_white() { 0 } else { 6 } + kind as usize]
+    }
+
+    ///
+    /// Get the kind of the piece.
+    ///
+    pub const fn kind(self) -> PieceKind {
+        PieceKind::ALL[self as usize % 6]
+    }
+
+    ///
+    /// Get the letter of the piece as used in FEN strings, uppercase for white and lowercase for
+    /// black.
+    ///
+    pub const fn to_char(self) -> char {
+        match self {
+            Piece::WhitePawn => 'P',
+            Piece::WhiteKnight => 'N',
+            Piece::WhiteBishop => 'B',
+            Piece::WhiteRook => 'R',
+            Piece::WhiteQueen => 'Q',
+            Piece::WhiteKing => 'K',
+            Piece::BlackPawn => 'p',
+            Piece::BlackKnight => 'n',
+            Piece::BlackBishop => 'b',
+            Piece::BlackRook => 'r',
+            Piece::BlackQueen => 'q',
+            Piece::BlackKing => 'k',
+        }
+    }
+
+    ///
+    /// Get the piece from its letter as used in FEN strings.
+    ///
+    /// # Arguments
+    ///
+    /// * `c` - The letter, uppercase for white and lowercase for black.
+    ///
+    /// # Returns
+    ///
+    /// The piece, or `None` if the letter is not a piece.
+    ///
+    pub const fn from_char(c: char) -> Option<Piece> {
+        match c {
+            'P' => Some(Piece::WhitePawn),
+            'N' => Some(Piece::WhiteKnight),
+            'B' => Some(Piece::WhiteBishop),
+            'R' => Some(Piece::WhiteRook),
+            'Q' => Some(Piece::WhiteQueen),
+            'K' => Some(Piece::WhiteKing),
+            'p' => Some(Piece::BlackPawn),
+            'n' => Some(Piece::BlackKnight),
+            'b' => Some(Piece::BlackBishop),
+            'r' => Some(Piece::BlackRook),
+            'q' => Some(Piece::BlackQueen),
+            'k' => Some(Piece::BlackKing),
+            _ => None,
+        }
+    }
+}
+
+///
+/// The kind of a piece, regardless of its color.
+///
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum PieceKind {
+    Pawn,
+    Knight,
+    Bishop,
+    Rook,
+    Queen,
+    King,
+}
+
+impl PieceKind {
+    ///
+    /// All kinds of pieces, from pawn to king.
+    ///
+    pub const ALL: [PieceKind; 6] = [
+        PieceKind::Pawn,
+        PieceKind::Knight,
+        PieceKind::Bishop,
+        PieceKind::Rook,
+        PieceKind::Queen,
+        PieceKind::King,
+    ];
+}
+
 /// A move of a piece on the board.
 #[allow(dead_code)]
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum Move {
     ///
     /// A Move::Normal move.
     ///
     /// # Arguments
     ///
-    /// * `from` - The position of the piece to be moved as a number between 0 and 63 (both included).
-    /// * `to` - The position to move the piece to as a number between 0 and 63 (both included).
+    /// * `from` - The square of the piece to be moved.
+    /// * `to` - The square to move the piece to.
     ///
-    Normal { from: u8, to: u8 },
+    Normal { from: Square, to: Square },
     ///
     /// A castling move.
     ///
     /// # Arguments
     ///
-    /// * `from` - The position of the king to be moved as a number between 0 and 63 (both included).
-    /// * `to` - The position to move the king to as a number between 0 and 63 (both included).
-    /// * `rook` - The position of the rook to be moved as a number between 0 and 63 (both included).
+    /// * `from` - The square of the king to be moved.
+    /// * `to` - The square to move the king to.
+    /// * `rook` - The square of the rook to be moved.
     ///
-    Castle { from: u8, to: u8, rook: u8 },
+    Castle {
+        from: Square,
+        to: Square,
+        rook: Square,
+    },
     ///
     /// An en passant move.
     ///
     /// # Arguments
     ///
-    /// * `from` - The position of the pawn to be moved as a number between 0 and 63 (both included).
-    /// * `to` - The position to move the pawn to as a number between 0 and 63 (both included).
-    /// * `captured` - The position of the pawn to be captured as a number between 0 and 63 (both included).
+    /// * `from` - The square of the pawn to be moved.
+    /// * `to` - The square to move the pawn to.
+    /// * `captured` - The square of the pawn to be captured.
     ///
-    EnPassant { from: u8, to: u8, captured: u8 },
+    EnPassant {
+        from: Square,
+        to: Square,
+        captured: Square,
+    },
     ///
     /// A promotion move.
     ///
     /// # Arguments
     ///
-    /// * `from` - The position of the pawn to be moved as a number between 0 and 63 (both included).
-    /// * `to` - The position to move the pawn to as a number between 0 and 63 (both included).
+    /// * `from` - The square of the pawn to be moved.
+    /// * `to` - The square to move the pawn to.
     /// * `promotion` - The piece to promote to as a character (b n r q B N R Q).
     ///
-    Promotion { from: u8, to: u8, promotion: Piece },
+    Promotion {
+        from: Square,
+        to: Square,
+        promotion: Piece,
+    },
+}
+
+impl Move {
+    ///
+    /// Get the square the moved piece comes from, being the king's square for castling.
+    ///
+    pub const fn from_square(self) -> Square {
+        match self {
+            Move::Normal { from, .. }
+            | Move::Castle { from, .. }
+            | Move::EnPassant { from, .. }
+            | Move::Promotion { from, .. } => from,
+        }
+    }
+
+    ///
+    /// Get the square the moved piece goes to, being the king's square for castling.
+    ///
+    pub const fn to_square(self) -> Square {
+        match self {
+            Move::Normal { to, .. }
+            | Move::Castle { to, .. }
+            | Move::EnPassant { to, .. }
+            | Move::Promotion { to, .. } => to,
+        }
+    }
+
+    ///
+    /// Get the move in the long algebraic notation of the UCI protocol.
+    ///
+    /// Castling is written as the move of the king and a promotion with the lowercase letter of
+    /// the promoted piece, as in `e1g1` and `e7e8q`.
+    ///
+    pub fn to_uci(self) -> String {
+        match self {
+            Move::Promotion {
+                from,
+                to,
+                promotion,
+            } => format!("{}{}{}", from, to, promotion.to_char().to_ascii_lowercase()),
+            _ => format!("{}{}", self.from_square(), self.to_square()),
+        }
+    }
+}
+
+///
+/// A subset of the legal moves, as generated by `Board::generate` for the stages of a search.
+///
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum MoveKind {
+    /// Captures, including en passant, and promotions to a queen.
+    Captures,
+    /// All moves that are not captures or promotions to a queen, including castling and
+    /// underpromotions.
+    Quiets,
+    /// The quiet moves that give check.
+    QuietChecks,
+    /// All moves when the side to move is in check, and none otherwise.
+    Evasions,
+}
+
+///
+/// The information needed to undo a move made with `Board::make`.
+///
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct UndoInfo {
+    /// The piece captured by the move.
+    captured: Option<Piece>,
+    /// The en passant square before the move.
+    en_passant: Option<u8>,
+    /// The castling rights before the move, in the order of `Board::castling_rights`.
+    castling: [bool; 4],
+    /// The halfmove clock before the move.
+    halfmove: u16,
+    /// The Zobrist key before the move.
+    hash: u64,
 }
 
 ///
@@ -146,7 +460,7 @@ pub enum Move {
 /// The bits are ordered first from left to right and next from top to bottom.
 ///
 #[allow(dead_code)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct Board {
     /// A bitboard representing the white pawns.
     white_pawns: u64,
@@ -181,7 +495,7 @@ pub struct Board {
     /// The position of the en passant square as a number between 0 and 63 (both included).
     en_passant: Option<u8>,
     /// A boolean representing whose turn it is.
-    pub white_turn: bool,
+    white_turn: bool,
     /// A boolean representing whether white can castle kingside.
     white_castle_kingside: bool,
     /// A boolean representing whether white can castle queenside.
@@ -191,9 +505,49 @@ pub struct Board {
     /// A boolean representing whether black can castle queenside.
     black_castle_queenside: bool,
     /// The number of halfmoves since the last capture or pawn advance.
-    pub halfmove: u8,
-    /// The number of the full move.
-    pub fullmove: u8,
+    halfmove: u16,
+    /// The number of the full move, starting at 1 and incremented after black's move.
+    fullmove: u16,
+    /// The Zobrist key of the position.
+    hash: u64,
+    /// The piece on every position, kept in sync with the bitboards.
+    mailbox: [Option<Piece>; 64],
+}
+
+///
+/// An iterator over the pieces on a board and their squares, from a1 to h8.
+///
+pub struct PieceIter<'a> {
+    /// The piece on every position of the board.
+    mailbox: &'a [Option<Piece>; 64],
+    /// The next position to look at.
+    position: u8,
+}
+
+impl Iterator for PieceIter<'_> {
+    type Item = (Square, Piece);
+
+    fn next(&mut self) -> Option<(Square, Piece)> {
+        while self.position < 64 {
+            let square = Square::new(self.position);
+            self.position += 1;
+
+            if let Some(piece) = self.mailbox[square.index() as usize] {
+                return Some((square, piece));
+            }
+        }
+
+        None
+    }
+}
+
+impl<'a> IntoIterator for &'a Board {
+    type Item = (Square, Piece);
+    type IntoIter = PieceIter<'a>;
+
+    fn into_iter(self) -> PieceIter<'a> {
+        self.iter()
+    }
 }
 
 #[allow(dead_code)]
@@ -213,20 +567,76 @@ impl Board {
     ///
     /// * `input` - The FEN string.
     ///
+    /// # Panics
+    ///
+    /// Panics if the FEN string is malformed. Use `Board::from_fen` to handle the error instead.
+    ///
     pub fn new(input: &str) -> Board {
-        let mut row = 7;
-        let mut col = 0;
+        match Board::from_fen(input) {
+            Ok(board) => board,
+            Err(error) => panic!("Invalid FEN string: {}", error),
+        }
+    }
+
+    ///
+    /// Creates a new board from a FEN string, returning an error if the FEN string is malformed.
+    ///
+    /// ```
+    /// use osprey::{Board, FenErrorKind, FenField};
+    ///
+    /// let error = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkx - 0 1").unwrap_err();
+    ///
+    /// assert_eq!(error.field, FenField::Castling);
+    /// assert_eq!(error.offset, 49);
+    /// assert_eq!(error.kind, FenErrorKind::InvalidCastling('x'));
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The FEN string.
+    ///
+    /// # Returns
+    ///
+    /// The board, or an error describing the field, offset and reason of the first problem found.
+    ///
+    pub fn from_fen(input: &str) -> Result<Board, FenError> {
+        // split FEN string by spaces, keeping the character offset of each field
+        let mut fen: Vec<(usize, &str)> = Vec::new();
+        let mut field_start: Option<(usize, usize)> = None;
+
+        for (offset, (index, c)) in input.char_indices().enumerate() {
+            if c.is_whitespace() {
+                if let Some((start, start_index)) = field_start.take() {
+                    fen.push((start, &input[start_index..index]));
+                }
+            } else if field_start.is_none() {
+                field_start = Some((offset, index));
+            }
+        }
+        if let Some((start, start_index)) = field_start {
+            fen.push((start, &input[start_index..]));
+        }
 
-        // split FEN string by spaces
-        let fen: Vec<&str> = input.split_whitespace().collect();
+        if fen.len() > 6 {
+            return Err(FenError::new(
+                FenField::FullMove,
+                fen[6].0,
+                FenErrorKind::UnexpectedField,
+            ));
+        }
+        if fen.len() < 6 {
+            let offset = input.chars().count();
+            let field = FenField::from_index(fen.len()).unwrap_or(FenField::FullMove);
+            return Err(FenError::new(field, offset, FenErrorKind::MissingField));
+        }
 
         // get FEN string parts
-        let fen_pieces = fen.get(0);
-        let fen_turn = fen.get(1);
-        let fen_castling = fen.get(2);
-        let fen_en_passant = fen.get(3);
-        let fen_half_move = fen.get(4);
-        let fen_full_move = fen.get(5);
+        let (pieces_offset, fen_pieces) = fen[0];
+        let (turn_offset, fen_turn) = fen[1];
+        let (castling_offset, fen_castling) = fen[2];
+        let (en_passant_offset, fen_en_passant) = fen[3];
+        let (half_move_offset, fen_half_move) = fen[4];
+        let (full_move_offset, fen_full_move) = fen[5];
 
         // initialize bitboards
         let mut white_pawns: u64 = 0;
@@ -243,108 +653,165 @@ impl Board {
         let mut black_king: u64 = 0;
 
         // initialize meta data
-        let white_turn;
         let mut white_castle_kingside = false;
         let mut white_castle_queenside = false;
         let mut black_castle_kingside = false;
         let mut black_castle_queenside = false;
         let mut en_passant: Option<u8> = None;
-        let halfmove;
-        let fullmove;
 
         // build bitboards from FEN string
-        match fen_pieces {
-            Some(fen_pieces) => {
-                for c in fen_pieces.chars() {
-                    if (c as u8) >= 48 && (c as u8) <= 57 {
-                        col += c as u8 - 48;
-                    } else if c == '/' {
-                        row -= 1;
-                        col = 0;
-                    } else {
-                        let pos: u64 = 1u64 << (row as u32 * 8 + col as u32);
-                        match c {
-                            'P' => white_pawns += pos,
-                            'N' => white_knights += pos,
-                            'B' => white_bishops += pos,
-                            'R' => white_rooks += pos,
-                            'Q' => white_queens += pos,
-                            'K' => white_king += pos,
-                            'p' => black_pawns += pos,
-                            'n' => black_knights += pos,
-                            'b' => black_bishops += pos,
-                            'r' => black_rooks += pos,
-                            'q' => black_queens += pos,
-                            'k' => black_king += pos,
-                            _ => panic!("Invalid FEN string"),
-                        }
-                        col += 1;
-                    }
+        let mut row: u8 = 7;
+        let mut col: u8 = 0;
+
+        for (i, c) in fen_pieces.chars().enumerate() {
+            let offset = pieces_offset + i;
+
+            if c == '/' {
+                if col < 8 {
+                    return Err(FenError::new(
+                        FenField::Pieces,
+                        offset,
+                        FenErrorKind::TooFewSquares { rank: row },
+                    ));
+                }
+                if row == 0 {
+                    return Err(FenError::new(
+                        FenField::Pieces,
+                        offset,
+                        FenErrorKind::TooManyRanks,
+                    ));
+                }
+                row -= 1;
+                col = 0;
+                continue;
+            }
+
+            let board = match c {
+                'P' => Some(&mut white_pawns),
+                'N' => Some(&mut white_knights),
+                'B' => Some(&mut white_bishops),
+                'R' => Some(&mut white_rooks),
+                'Q' => Some(&mut white_queens),
+                'K' => Some(&mut white_king),
+                'p' => Some(&mut black_pawns),
+                'n' => Some(&mut black_knights),
+                'b' => Some(&mut black_bishops),
+                'r' => Some(&mut black_rooks),
+                'q' => Some(&mut black_queens),
+                'k' => Some(&mut black_king),
+                '1'..='8' => None,
+                _ => {
+                    return Err(FenError::new(
+                        FenField::Pieces,
+                        offset,
+                        FenErrorKind::UnknownPiece(c),
+                    ))
                 }
+            };
+
+            let squares = match board {
+                Some(_) => 1,
+                None => c as u8 - b'0',
+            };
+
+            if col + squares > 8 {
+                return Err(FenError::new(
+                    FenField::Pieces,
+                    offset,
+                    FenErrorKind::TooManySquares { rank: row },
+                ));
+            }
+
+            if let Some(board) = board {
+                *board |= 1u64 << (row as u32 * 8 + col as u32);
             }
-            None => panic!("Invalid FEN string"),
+
+            col += squares;
         }
 
-        // set turn
-        match fen_turn {
-            Some(fen_turn) => match *fen_turn {
-                "w" => white_turn = true,
-                "b" => white_turn = false,
-                _ => panic!("Invalid FEN string"),
-            },
-            None => panic!("Invalid FEN string"),
+        let pieces_end = pieces_offset + fen_pieces.chars().count();
+        if col < 8 {
+            return Err(FenError::new(
+                FenField::Pieces,
+                pieces_end,
+                FenErrorKind::TooFewSquares { rank: row },
+            ));
         }
+        if row > 0 {
+            return Err(FenError::new(
+                FenField::Pieces,
+                pieces_end,
+                FenErrorKind::TooFewRanks,
+            ));
+        }
+
+        // set turn
+        let white_turn = match fen_turn {
+            "w" => true,
+            "b" => false,
+            _ => {
+                return Err(FenError::new(
+                    FenField::Turn,
+                    turn_offset,
+                    FenErrorKind::InvalidTurn,
+                ))
+            }
+        };
 
         // set castling
-        match fen_castling {
-            Some(fen_castling) => {
-                for c in fen_castling.chars() {
-                    match c {
-                        'K' => white_castle_kingside = true,
-                        'Q' => white_castle_queenside = true,
-                        'k' => black_castle_kingside = true,
-                        'q' => black_castle_queenside = true,
-                        '-' => (),
-                        _ => panic!("Invalid FEN string"),
+        if fen_castling != "-" {
+            for (i, c) in fen_castling.chars().enumerate() {
+                let right = match c {
+                    'K' => &mut white_castle_kingside,
+                    'Q' => &mut white_castle_queenside,
+                    'k' => &mut black_castle_kingside,
+                    'q' => &mut black_castle_queenside,
+                    _ => {
+                        return Err(FenError::new(
+                            FenField::Castling,
+                            castling_offset + i,
+                            FenErrorKind::InvalidCastling(c),
+                        ))
                     }
+                };
+
+                if *right {
+                    return Err(FenError::new(
+                        FenField::Castling,
+                        castling_offset + i,
+                        FenErrorKind::DuplicateCastling(c),
+                    ));
                 }
+                *right = true;
             }
-            None => panic!("Invalid FEN string"),
         }
 
         // set en passant
-        match fen_en_passant {
-            Some(fen_en_passant) => {
-                if *fen_en_passant != "-" {
-                    let col = fen_en_passant.chars().nth(0).unwrap() as u8 - 97;
-                    let row = fen_en_passant.chars().nth(1).unwrap() as u8 - 49;
-                    en_passant = Some(row * 8 + col);
+        if fen_en_passant != "-" {
+            match fen_en_passant.as_bytes() {
+                [file @ b'a'..=b'h', rank @ (b'3' | b'6')] => {
+                    en_passant = Some((rank - b'1') * 8 + (file - b'a'));
                 }
-            }
-            None => panic!("Invalid FEN string"),
-        }
-
-        // set halfmove and fullmove
-        match fen_half_move {
-            Some(fen_half_move) => {
-                let half_move = fen_half_move.parse::<u8>();
-                match half_move {
-                    Ok(half_move) => halfmove = half_move,
-                    Err(_) => panic!("Invalid FEN string"),
+                _ => {
+                    return Err(FenError::new(
+                        FenField::EnPassant,
+                        en_passant_offset,
+                        FenErrorKind::InvalidEnPassant,
+                    ))
                 }
             }
-            None => panic!("Invalid FEN string"),
         }
 
-        match fen_full_move {
-            Some(fen_full_move) => {
-                let full_move = fen_full_move.parse::<u8>();
-                match full_move {
-                    Ok(full_move) => fullmove = full_move,
-                    Err(_) => panic!("Invalid FEN string"),
-                }
-            }
-            None => panic!("Invalid FEN string"),
+        // set halfmove and fullmove
+        let halfmove = Board::parse_counter(fen_half_move, FenField::HalfMove, half_move_offset)?;
+        let fullmove = Board::parse_counter(fen_full_move, FenField::FullMove, full_move_offset)?;
+
+        if fullmove == 0 {
+            return Err(FenError::new(
+                FenField::FullMove,
+                full_move_offset,
+                FenErrorKind::CounterOutOfRange,
+            ));
         }
 
         // set white and black pieces
@@ -354,7 +821,7 @@ impl Board {
             black_pawns | black_knights | black_bishops | black_rooks | black_queens;
         let empty_squares: u64 = !(white_pieces | black_pieces | white_king | black_king);
 
-        Board {
+        let mut board = Board {
             white_pawns,
             white_knights,
             white_bishops,
@@ -378,58 +845,285 @@ impl Board {
             en_passant,
             halfmove,
             fullmove,
-        }
+            hash: 0,
+            mailbox: [None; 64],
+        };
+
+        board.hash = board.compute_hash();
+        board.mailbox = board.compute_mailbox();
+
+        Ok(board)
     }
 
     ///
-    /// Exports the board as a FEN string.
+    /// Creates a board from its pieces and state, as done by `BoardBuilder`.
     ///
-    pub fn export_fen(&self) -> String {
-        let mut fen = String::new();
-        let mut empty = 0;
+    /// # Arguments
+    ///
+    /// * `pieces` - The pieces and their squares, each square at most once.
+    /// * `turn` - The side to move.
+    /// * `castling` - The castling rights of both sides.
+    /// * `en_passant` - The en passant square.
+    /// * `halfmove` - The number of halfmoves since the last capture or pawn advance.
+    /// * `fullmove` - The number of the full move.
+    ///
+    pub(crate) fn from_parts(
+        pieces: impl IntoIterator<Item = (Square, Piece)>,
+        turn: Color,
+        castling: CastlingRights,
+        en_passant: Option<Square>,
+        halfmove: u16,
+        fullmove: u16,
+    ) -> Board {
+        let mut board = Board {
+            white_pawns: 0,
+            white_knights: 0,
+            white_bishops: 0,
+            white_rooks: 0,
+            white_queens: 0,
+            white_king: 0,
+            black_pawns: 0,
+            black_knights: 0,
+            black_bishops: 0,
+            black_rooks: 0,
+            black_queens: 0,
+            black_king: 0,
+            white_pieces: 0,
+            black_pieces: 0,
+            empty_squares: !0,
+            en_passant: en_passant.map(Square::index),
+            white_turn: turn.is_white(),
+            white_castle_kingside: castling.white_kingside,
+            white_castle_queenside: castling.white_queenside,
+            black_castle_kingside: castling.black_kingside,
+            black_castle_queenside: castling.black_queenside,
+            halfmove,
+            fullmove,
+            hash: 0,
+            mailbox: [None; 64],
+        };
 
-        for row in 0..8 {
-            for col in 0..8 {
-                let piece = self.square(row * 8 + col);
-                match piece {
-                    Some(piece) => {
-                        if empty > 0 {
-                            fen.push_str(&empty.to_string());
-                            empty = 0;
-                        }
-                        fen.push(piece);
-                    }
-                    None => {
-                        empty += 1;
-                    }
-                }
-            }
-            if empty > 0 {
-                fen.push_str(&empty.to_string());
-                empty = 0;
-            }
-            if row > 0 {
-                fen.push('/');
-            }
+        // toggling keeps the sides, empty squares and mailbox in sync with the bitboards
+        for (square, piece) in pieces {
+            board.toggle(piece, square);
         }
 
-        fen.push(' ');
-
-        if self.white_turn {
-            fen.push('w');
-        } else {
-            fen.push('b');
-        }
+        board.hash = board.compute_hash();
 
-        fen.push(' ');
+        board
+    }
 
-        if self.white_castle_kingside {
-            fen.push('K');
+    ///
+    /// Parses a halfmove or fullmove counter of a FEN string.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The counter field.
+    /// * `field` - The field being parsed.
+    /// * `offset` - The character offset of the field in the FEN string.
+    ///
+    fn parse_counter(input: &str, field: FenField, offset: usize) -> Result<u16, FenError> {
+        if input.is_empty() || !input.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(FenError::new(field, offset, FenErrorKind::InvalidCounter));
         }
-        if self.white_castle_queenside {
-            fen.push('Q');
+
+        match input.parse::<u16>() {
+            Ok(counter) => Ok(counter),
+            Err(_) => Err(FenError::new(
+                field,
+                offset,
+                FenErrorKind::CounterOutOfRange,
+            )),
         }
-        if self.black_castle_kingside {
+    }
+
+    ///
+    /// Creates a new board from a FEN string, returning an error if the FEN string is malformed
+    /// or does not describe a legal chess position.
+    ///
+    /// ```
+    /// use osprey::{Board, PositionError, PositionIssue};
+    ///
+    /// let error = Board::from_fen_strict("4k3/8/8/8/8/8/8/4K2R w KQ - 0 1").unwrap_err();
+    ///
+    /// assert_eq!(
+    ///     error,
+    ///     PositionError::Illegal(vec![PositionIssue::InvalidCastlingRights {
+    ///         white: true,
+    ///         kingside: false,
+    ///     }])
+    /// );
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The FEN string.
+    ///
+    /// # Returns
+    ///
+    /// The board, or an error describing why the FEN string was rejected.
+    ///
+    pub fn from_fen_strict(input: &str) -> Result<Board, PositionError> {
+        let board = Board::from_fen(input)?;
+        let issues = board.validate();
+
+        if issues.is_empty() {
+            Ok(board)
+        } else {
+            Err(PositionError::Illegal(issues))
+        }
+    }
+
+    ///
+    /// Checks whether the board describes a legal chess position.
+    ///
+    /// # Returns
+    ///
+    /// A vector of all issues found, which is empty if the position is legal.
+    ///
+    pub fn validate(&self) -> Vec<PositionIssue> {
+        let mut issues: Vec<PositionIssue> = Vec::new();
+
+        // kings
+        for (white, king) in [(true, self.white_king), (false, self.black_king)] {
+            if king.count_ones() != 1 {
+                issues.push(PositionIssue::KingCount {
+                    white,
+                    count: king.count_ones(),
+                });
+            }
+        }
+
+        // pawns on the back ranks
+        let back_rank_pawns = (self.white_pawns | self.black_pawns) & (RANK_1 | RANK_8);
+
+        for i in SetBits(back_rank_pawns) {
+            issues.push(PositionIssue::PawnOnBackRank {
+                square: Square::new(i),
+            });
+        }
+
+        // castling rights
+        let white_king_home = self.white_king & (1u64 << 4) != 0;
+        let black_king_home = self.black_king & (1u64 << 60) != 0;
+
+        // (white, kingside, castling right, king and rook on their home squares)
+        let castling = [
+            (
+                true,
+                true,
+                self.white_castle_kingside,
+                white_king_home && self.white_rooks & (1u64 << 7) != 0,
+            ),
+            (
+                true,
+                false,
+                self.white_castle_queenside,
+                white_king_home && self.white_rooks & (1u64 << 0) != 0,
+            ),
+            (
+                false,
+                true,
+                self.black_castle_kingside,
+                black_king_home && self.black_rooks & (1u64 << 63) != 0,
+            ),
+            (
+                false,
+                false,
+                self.black_castle_queenside,
+                black_king_home && self.black_rooks & (1u64 << 56) != 0,
+            ),
+        ];
+
+        for (white, kingside, right, home) in castling {
+            if right && !home {
+                issues.push(PositionIssue::InvalidCastlingRights { white, kingside });
+            }
+        }
+
+        // en passant
+        if let Some(en_passant) = self.en_passant {
+            let rank = if self.white_turn { RANK_6 } else { RANK_3 };
+
+            let valid = rank & (1u64 << en_passant) != 0 && {
+                // the squares the pawn moved from and to
+                let (from, to, pawns) = if self.white_turn {
+                    (en_passant + 8, en_passant - 8, self.black_pawns)
+                } else {
+                    (en_passant - 8, en_passant + 8, self.white_pawns)
+                };
+
+                pawns & (1u64 << to) != 0
+                    && self.empty_squares & (1u64 << en_passant) != 0
+                    && self.empty_squares & (1u64 << from) != 0
+            };
+
+            if !valid {
+                issues.push(PositionIssue::InvalidEnPassant {
+                    square: Square::new(en_passant),
+                });
+            }
+        }
+
+        // side not to move in check
+        if (self.white_turn && self.black_king & self.unsafe_squares::<false>() != 0)
+            || (!self.white_turn && self.white_king & self.unsafe_squares::<true>() != 0)
+        {
+            issues.push(PositionIssue::OpponentInCheck);
+        }
+
+        issues
+    }
+
+    ///
+    /// Exports the board as a FEN string.
+    ///
+    pub fn export_fen(&self) -> String {
+        let mut fen = String::new();
+        let mut empty = 0;
+
+        for row in (0..8).rev() {
+            for col in 0..8 {
+                let piece = self.square(row * 8 + col);
+                match piece {
+                    Some(piece) => {
+                        if empty > 0 {
+                            fen.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        fen.push(piece);
+                    }
+                    None => {
+                        empty += 1;
+                    }
+                }
+            }
+            if empty > 0 {
+                fen.push_str(&empty.to_string());
+                empty = 0;
+            }
+            if row > 0 {
+                fen.push('/');
+            }
+        }
+
+        fen.push(' ');
+
+        if self.white_turn {
+            fen.push('w');
+        } else {
+            fen.push('b');
+        }
+
+        fen.push(' ');
+
+        if self.white_castle_kingside {
+            fen.push('K');
+        }
+        if self.white_castle_queenside {
+            fen.push('Q');
+        }
+        if self.black_castle_kingside {
             fen.push('k');
         }
         if self.black_castle_queenside {
@@ -447,8 +1141,8 @@ impl Board {
 
         match self.en_passant {
             Some(en_passant) => {
-                let row: u8 = (en_passant / 7) as u8;
-                let col: u8 = (en_passant % 7) as u8;
+                let row: u8 = en_passant / 8;
+                let col: u8 = en_passant % 8;
 
                 fen.push((col + 97) as char);
                 fen.push((row + 49) as char);
@@ -470,55 +1164,313 @@ impl Board {
     }
 
     ///
-    /// The the piece on the given square.
+    /// Get the halfmove clock.
     ///
-    /// # Arguments
+    /// # Returns
     ///
-    /// * `position` - The position of the square as a number between 0 and 63 (both included).
+    /// The number of halfmoves since the last capture or pawn advance, as used by the fifty-move
+    /// and seventy-five-move rules.
+    ///
+    pub fn halfmove(&self) -> u16 {
+        self.halfmove
+    }
+
+    ///
+    /// Get the fullmove number.
     ///
     /// # Returns
     ///
-    /// The character representing the piece on the given square.
-    /// [p, b, n, r, q, k, P, B, N, R, Q, K] for [black pawn, black bishop, black knight, black rook, black queen, black king, white pawn, white bishop, white knight, white rook, white queen, white king].
-    fn square(&self, position: u8) -> Option<char> {
-        if self.white_pawns & (1u64 << position) != 0 {
-            return Some('P');
-        }
-        if self.white_knights & (1u64 << position) != 0 {
-            return Some('N');
-        }
-        if self.white_bishops & (1u64 << position) != 0 {
-            return Some('B');
-        }
-        if self.white_rooks & (1u64 << position) != 0 {
-            return Some('R');
-        }
-        if self.white_queens & (1u64 << position) != 0 {
-            return Some('Q');
+    /// The number of the full move, starting at 1 and incremented after black's move.
+    ///
+    pub fn fullmove(&self) -> u16 {
+        self.fullmove
+    }
+
+    ///
+    /// Get the en passant square.
+    ///
+    /// # Returns
+    ///
+    /// The square a pawn passed over with a double push on the last move, or `None` if the last
+    /// move was not a double pawn push.
+    ///
+    pub fn en_passant(&self) -> Option<Square> {
+        self.en_passant.map(Square::new)
+    }
+
+    ///
+    /// Get the castling rights of both sides.
+    ///
+    pub fn castling_rights(&self) -> CastlingRights {
+        CastlingRights {
+            white_kingside: self.white_castle_kingside,
+            white_queenside: self.white_castle_queenside,
+            black_kingside: self.black_castle_kingside,
+            black_queenside: self.black_castle_queenside,
         }
-        if self.white_king & (1u64 << position) != 0 {
-            return Some('K');
+    }
+
+    ///
+    /// Get the color of the side to move.
+    ///
+    pub fn turn(&self) -> Color {
+        Color::from_white(self.white_turn)
+    }
+
+    ///
+    /// Get the piece on a square.
+    ///
+    /// # Arguments
+    ///
+    /// * `square` - The square.
+    ///
+    /// # Returns
+    ///
+    /// The color and kind of the piece, or `None` if the square is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use osprey::{Board, Color, PieceKind, Square};
+    ///
+    /// let board = Board::new("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+    ///
+    /// assert_eq!(board.piece_at(Square::D8), Some((Color::Black, PieceKind::Queen)));
+    /// assert_eq!(board.piece_at(Square::E4), None);
+    /// ```
+    ///
+    pub fn piece_at(&self, square: Square) -> Option<(Color, PieceKind)> {
+        self.piece_on(square)
+            .map(|piece| (piece.color(), piece.kind()))
+    }
+
+    ///
+    /// Get the pieces of a color and kind.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The color of the pieces.
+    /// * `kind` - The kind of the pieces.
+    ///
+    pub fn pieces(&self, color: Color, kind: PieceKind) -> Bitboard {
+        Bitboard(self.piece_boards()[Piece::new(color, kind) as usize])
+    }
+
+    ///
+    /// Get all occupied squares.
+    ///
+    pub fn occupied(&self) -> Bitboard {
+        Bitboard(!self.empty_squares)
+    }
+
+    ///
+    /// Get the square of the king of a side.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The color of the king.
+    ///
+    /// # Returns
+    ///
+    /// The square of the king, or `None` if the side has no king.
+    ///
+    pub fn king_square(&self, color: Color) -> Option<Square> {
+        self.pieces(color, PieceKind::King).first()
+    }
+
+    ///
+    /// Get an iterator over all pieces on the board, from a1 to h8.
+    ///
+    pub fn iter(&self) -> PieceIter<'_> {
+        PieceIter {
+            mailbox: &self.mailbox,
+            position: 0,
         }
-        if self.black_pawns & (1u64 << position) != 0 {
-            return Some('p');
+    }
+
+    ///
+    /// Get the bitboards of a side.
+    ///
+    /// # Arguments
+    ///
+    /// * `white` - Whether the side is white.
+    ///
+    fn side(&self, white: bool) -> Side {
+        if white {
+            Side {
+                pawns: self.white_pawns,
+                knights: self.white_knights,
+                bishops: self.white_bishops,
+                rooks: self.white_rooks,
+                queens: self.white_queens,
+                king: self.white_king,
+                pieces: self.white_pieces,
+            }
+        } else {
+            Side {
+                pawns: self.black_pawns,
+                knights: self.black_knights,
+                bishops: self.black_bishops,
+                rooks: self.black_rooks,
+                queens: self.black_queens,
+                king: self.black_king,
+                pieces: self.black_pieces,
+            }
         }
-        if self.black_knights & (1u64 << position) != 0 {
-            return Some('n');
+    }
+
+    ///
+    /// Get the Zobrist key of the position.
+    ///
+    /// The key depends on the pieces, the side to move, the castling rights and the en passant
+    /// file if an en passant capture is possible, but not on the move counters.
+    ///
+    /// # Returns
+    ///
+    /// A 64-bit key identifying the position.
+    ///
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    ///
+    /// Computes the Zobrist key of the position from scratch.
+    ///
+    /// # Returns
+    ///
+    /// A 64-bit key identifying the position.
+    ///
+    fn compute_hash(&self) -> u64 {
+        let mut hash = 0;
+
+        for (piece, board) in self.piece_boards().iter().enumerate() {
+            let mut board = *board;
+
+            while board != 0 {
+                hash ^= zobrist::PIECES[piece][board.trailing_zeros() as usize];
+                board &= board - 1;
+            }
         }
-        if self.black_bishops & (1u64 << position) != 0 {
-            return Some('b');
+
+        if !self.white_turn {
+            hash ^= zobrist::SIDE;
         }
-        if self.black_rooks & (1u64 << position) != 0 {
-            return Some('r');
+
+        hash ^ self.castling_hash() ^ self.en_passant_hash()
+    }
+
+    ///
+    /// Computes the piece on every position from scratch, as kept in sync by the moves.
+    ///
+    fn compute_mailbox(&self) -> [Option<Piece>; 64] {
+        let mut mailbox = [None; 64];
+
+        for (piece, board) in Piece::ALL.into_iter().zip(self.piece_boards()) {
+            for i in SetBits(board) {
+                mailbox[i as usize] = Some(piece);
+            }
         }
-        if self.black_queens & (1u64 << position) != 0 {
-            return Some('q');
+
+        mailbox
+    }
+
+    ///
+    /// Get the part of the Zobrist key for the castling rights.
+    ///
+    fn castling_hash(&self) -> u64 {
+        self.castling_flags()
+            .iter()
+            .zip(zobrist::CASTLING.iter())
+            .filter(|(right, _)| **right)
+            .fold(0, |hash, (_, key)| hash ^ key)
+    }
+
+    ///
+    /// Get the part of the Zobrist key for the en passant square.
+    ///
+    /// The en passant file is only hashed if a pawn of the side to move can capture en passant.
+    ///
+    fn en_passant_hash(&self) -> u64 {
+        match self.capturable_en_passant() {
+            Some(en_passant) => zobrist::EN_PASSANT[en_passant as usize % 8],
+            None => 0,
         }
-        if self.black_king & (1u64 << position) != 0 {
-            return Some('k');
+    }
+
+    ///
+    /// Get the castling rights as flags.
+    ///
+    /// # Returns
+    ///
+    /// The castling rights in the order white kingside, white queenside, black kingside and
+    /// black queenside.
+    ///
+    pub(crate) fn castling_flags(&self) -> [bool; 4] {
+        [
+            self.white_castle_kingside,
+            self.white_castle_queenside,
+            self.black_castle_kingside,
+            self.black_castle_queenside,
+        ]
+    }
+
+    ///
+    /// Get the en passant square if a pawn of the side to move is able to capture on it.
+    ///
+    /// # Returns
+    ///
+    /// The en passant square as a number between 0 and 63 (both included), or `None` if there is
+    /// no en passant square or no pawn attacks it.
+    ///
+    pub(crate) fn capturable_en_passant(&self) -> Option<u8> {
+        let en_passant = self.en_passant?;
+
+        let attacks = Board::pawn_attacks(self.side(self.white_turn).pawns, self.white_turn);
+
+        if attacks & (1u64 << en_passant) != 0 {
+            Some(en_passant)
+        } else {
+            None
         }
+    }
+
+    ///
+    /// Get the bitboards of all pieces.
+    ///
+    /// # Returns
+    ///
+    /// An array of the bitboards in the order of `Piece`.
+    ///
+    pub(crate) fn piece_boards(&self) -> [u64; 12] {
+        [
+            self.white_pawns,
+            self.white_knights,
+            self.white_bishops,
+            self.white_rooks,
+            self.white_queens,
+            self.white_king,
+            self.black_pawns,
+            self.black_knights,
+            self.black_bishops,
+            self.black_rooks,
+            self.black_queens,
+            self.black_king,
+        ]
+    }
 
-        return None;
+    ///
+    /// The the piece on the given square.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The position of the square as a number between 0 and 63 (both included).
+    ///
+    /// # Returns
+    ///
+    /// The character representing the piece on the given square.
+    /// [p, b, n, r, q, k, P, B, N, R, Q, K] for [black pawn, black bishop, black knight, black rook, black queen, black king, white pawn, white bishop, white knight, white rook, white queen, white king].
+    fn square(&self, position: u8) -> Option<char> {
+        self.mailbox[position as usize].map(Piece::to_char)
     }
 
     ///
@@ -532,6 +1484,7 @@ impl Board {
     ///
     /// A new board if the move is legal, otherwise the old board.
     ///
+    #[allow(clippy::result_large_err)]
     pub fn make_move(&self, m: &Move) -> Result<Board, Board> {
         // initialize meta data
         let mut new_en_passant = None;
@@ -540,8 +1493,6 @@ impl Board {
         let mut new_white_castle_queenside = self.white_castle_queenside;
         let mut new_black_castle_kingside = self.black_castle_kingside;
         let mut new_black_castle_queenside = self.black_castle_queenside;
-        let new_halfmove = self.halfmove + 1;
-        let new_fullmove = self.fullmove + 1;
 
         // set new boards
         let new_white_pawns = self.move_board(m, Piece::WhitePawn);
@@ -559,15 +1510,23 @@ impl Board {
         let new_black_king = self.move_board(m, Piece::BlackKing);
 
         // get from and to
-        let (from, to) = match m {
-            Move::Normal { from, to } => (*from, *to),
-            Move::Castle { from, to, .. } => (*from, *to),
-            Move::EnPassant { from, to, .. } => (*from, *to),
-            Move::Promotion {
-                from,
-                to,
-                ..
-            } => (*from, *to),
+        let (from, to) = (m.from_square().index(), m.to_square().index());
+
+        // halfmove clock is reset by pawn moves and captures
+        let pawn_move = (1u64 << from & (self.white_pawns | self.black_pawns)) != 0;
+        let capture = (1u64 << to & !self.empty_squares) != 0;
+
+        let new_halfmove = if pawn_move || capture {
+            0
+        } else {
+            self.halfmove.saturating_add(1)
+        };
+
+        // fullmove number is incremented after black's move
+        let new_fullmove = if self.white_turn {
+            self.fullmove
+        } else {
+            self.fullmove.saturating_add(1)
         };
 
         // en passant
@@ -618,7 +1577,7 @@ impl Board {
             !(new_white_pieces | new_black_pieces | new_white_king | new_black_king);
 
         // create new board
-        let new_board = Board {
+        let mut new_board = Board {
             white_pawns: new_white_pawns,
             black_pawns: new_black_pawns,
             white_knights: new_white_knights,
@@ -642,1090 +1601,2041 @@ impl Board {
             black_castle_queenside: new_black_castle_queenside,
             halfmove: new_halfmove,
             fullmove: new_fullmove,
+            hash: 0,
+            mailbox: self.mailbox,
         };
 
+        // update hash and mailbox
+        let mut new_hash = self.hash ^ zobrist::SIDE;
+        let mut changed_squares = 0;
+
+        for (piece, (old, new)) in self
+            .piece_boards()
+            .iter()
+            .zip(new_board.piece_boards().iter())
+            .enumerate()
+        {
+            let mut changed = old ^ new;
+            changed_squares |= changed;
+
+            while changed != 0 {
+                new_hash ^= zobrist::PIECES[piece][changed.trailing_zeros() as usize];
+                changed &= changed - 1;
+            }
+        }
+
+        new_hash ^= self.castling_hash() ^ new_board.castling_hash();
+        new_hash ^= self.en_passant_hash() ^ new_board.en_passant_hash();
+
+        new_board.hash = new_hash;
+
+        for i in SetBits(changed_squares) {
+            new_board.mailbox[i as usize] = Piece::ALL
+                .into_iter()
+                .zip(new_board.piece_boards())
+                .find(|(_, board)| board & (1u64 << i) != 0)
+                .map(|(piece, _)| piece);
+        }
+
+        debug_assert_eq!(new_board.hash, new_board.compute_hash());
+        debug_assert_eq!(new_board.mailbox, new_board.compute_mailbox());
+
         // check if move is legal
-        if (new_board.white_king & new_board.unsafe_w() == 0 && self.white_turn)
-            || (new_board.black_king & new_board.unsafe_b() == 0 && !self.white_turn)
+        if (new_board.white_king & new_board.unsafe_squares::<true>() == 0 && self.white_turn)
+            || (new_board.black_king & new_board.unsafe_squares::<false>() == 0 && !self.white_turn)
         {
             // return new board
-            return Ok(new_board);
+            Ok(new_board)
         } else {
             // return old board with error
-            return Err(self.clone());
+            Err(*self)
+        }
+    }
+
+    ///
+    /// Make a move on the board in place.
+    ///
+    /// Unlike `make_move` only the bitboards of the moved and captured pieces are modified and
+    /// the legality of the move is not checked, so the move should come from `legal_moves`.
+    ///
+    /// # Arguments
+    ///
+    /// * `m` - The move to make.
+    ///
+    /// # Returns
+    ///
+    /// The information needed to undo the move with `unmake`.
+    ///
+    pub fn make(&mut self, m: Move) -> UndoInfo {
+        let mut undo = UndoInfo {
+            captured: None,
+            en_passant: self.en_passant,
+            castling: self.castling_flags(),
+            halfmove: self.halfmove,
+            hash: self.hash,
+        };
+
+        let old_hash = self.castling_hash() ^ self.en_passant_hash();
+
+        let (from, to) = (m.from_square().index(), m.to_square().index());
+
+        // halfmove clock is reset by pawn moves and captures
+        let pawn_move = (1u64 << from & (self.white_pawns | self.black_pawns)) != 0;
+        let capture = (1u64 << to & !self.empty_squares) != 0;
+
+        self.halfmove = if pawn_move || capture {
+            0
+        } else {
+            self.halfmove.saturating_add(1)
+        };
+
+        // fullmove number is incremented after black's move
+        if !self.white_turn {
+            self.fullmove = self.fullmove.saturating_add(1);
+        }
+
+        // en passant
+        self.en_passant = None;
+
+        if to.abs_diff(from) == 16 {
+            if (1u64 << from & self.black_pawns) != 0 {
+                self.en_passant = Some(to + 8);
+            } else if (1u64 << from & self.white_pawns) != 0 {
+                self.en_passant = Some(to - 8);
+            }
+        }
+
+        // castling
+        if (1u64 << from & self.white_king) != 0 {
+            self.white_castle_kingside = false;
+            self.white_castle_queenside = false;
+        } else if (1u64 << from & self.black_king) != 0 {
+            self.black_castle_kingside = false;
+            self.black_castle_queenside = false;
+        }
+
+        if ((1u64 << from | 1u64 << to) & self.white_rooks & (1u64 << 0)) != 0 {
+            self.white_castle_queenside = false;
+        } else if ((1u64 << from | 1u64 << to) & self.white_rooks & 1u64 << 7) != 0 {
+            self.white_castle_kingside = false;
+        }
+
+        if ((1u64 << from | 1u64 << to) & self.black_rooks & (1u64 << 56)) != 0 {
+            self.black_castle_queenside = false;
+        } else if ((1u64 << from | 1u64 << to) & self.black_rooks & (1u64 << 63)) != 0 {
+            self.black_castle_kingside = false;
+        }
+
+        // move the pieces
+        match m {
+            Move::Normal { from, to } => {
+                undo.captured = self.piece_on(to);
+                if let Some(captured) = undo.captured {
+                    self.toggle(captured, to);
+                }
+
+                if let Some(piece) = self.piece_on(from) {
+                    self.toggle(piece, from);
+                    self.toggle(piece, to);
+                }
+            }
+            Move::Castle { from, to, rook } => {
+                let new_rook = Board::castled_rook(from, to);
+
+                if let Some(king) = self.piece_on(from) {
+                    self.toggle(king, from);
+                    self.toggle(king, to);
+                }
+                if let Some(rook_piece) = self.piece_on(rook) {
+                    self.toggle(rook_piece, rook);
+                    self.toggle(rook_piece, new_rook);
+                }
+            }
+            Move::EnPassant { from, to, captured } => {
+                undo.captured = self.piece_on(captured);
+                if let Some(captured_piece) = undo.captured {
+                    self.toggle(captured_piece, captured);
+                }
+
+                if let Some(pawn) = self.piece_on(from) {
+                    self.toggle(pawn, from);
+                    self.toggle(pawn, to);
+                }
+            }
+            Move::Promotion {
+                from,
+                to,
+                promotion,
+            } => {
+                undo.captured = self.piece_on(to);
+                if let Some(captured) = undo.captured {
+                    self.toggle(captured, to);
+                }
+
+                if let Some(pawn) = self.piece_on(from) {
+                    self.toggle(pawn, from);
+                }
+                self.toggle(promotion, to);
+            }
+        }
+
+        self.white_turn = !self.white_turn;
+
+        self.hash ^= zobrist::SIDE ^ old_hash ^ self.castling_hash() ^ self.en_passant_hash();
+
+        debug_assert_eq!(self.hash, self.compute_hash());
+        debug_assert_eq!(self.mailbox, self.compute_mailbox());
+
+        undo
+    }
+
+    ///
+    /// Undo a move made with `make`.
+    ///
+    /// # Arguments
+    ///
+    /// * `m` - The move to undo. This should be the last move made.
+    /// * `undo` - The information returned by `make` when making the move.
+    ///
+    pub fn unmake(&mut self, m: Move, undo: UndoInfo) {
+        self.white_turn = !self.white_turn;
+
+        match m {
+            Move::Normal { from, to } => {
+                if let Some(piece) = self.piece_on(to) {
+                    self.toggle(piece, to);
+                    self.toggle(piece, from);
+                }
+
+                if let Some(captured) = undo.captured {
+                    self.toggle(captured, to);
+                }
+            }
+            Move::Castle { from, to, rook } => {
+                let new_rook = Board::castled_rook(from, to);
+
+                if let Some(king) = self.piece_on(to) {
+                    self.toggle(king, to);
+                    self.toggle(king, from);
+                }
+                if let Some(rook_piece) = self.piece_on(new_rook) {
+                    self.toggle(rook_piece, new_rook);
+                    self.toggle(rook_piece, rook);
+                }
+            }
+            Move::EnPassant { from, to, captured } => {
+                if let Some(pawn) = self.piece_on(to) {
+                    self.toggle(pawn, to);
+                    self.toggle(pawn, from);
+                }
+
+                if let Some(captured_piece) = undo.captured {
+                    self.toggle(captured_piece, captured);
+                }
+            }
+            Move::Promotion {
+                from,
+                to,
+                promotion,
+            } => {
+                let pawn = if self.white_turn {
+                    Piece::WhitePawn
+                } else {
+                    Piece::BlackPawn
+                };
+
+                self.toggle(promotion, to);
+                self.toggle(pawn, from);
+
+                if let Some(captured) = undo.captured {
+                    self.toggle(captured, to);
+                }
+            }
+        }
+
+        if !self.white_turn {
+            self.fullmove = self.fullmove.saturating_sub(1);
+        }
+
+        [
+            self.white_castle_kingside,
+            self.white_castle_queenside,
+            self.black_castle_kingside,
+            self.black_castle_queenside,
+        ] = undo.castling;
+        self.en_passant = undo.en_passant;
+        self.halfmove = undo.halfmove;
+        self.hash = undo.hash;
+    }
+
+    ///
+    /// Get the square a castling rook is moved to, being next to the king on the side of the
+    /// rook.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The square the king is moved from.
+    /// * `to` - The square the king is moved to.
+    ///
+    fn castled_rook(from: Square, to: Square) -> Square {
+        if to > from {
+            Square::new(to.index() - 1)
+        } else {
+            Square::new(to.index() + 1)
+        }
+    }
+
+    ///
+    /// Get the piece on a square.
+    ///
+    /// # Arguments
+    ///
+    /// * `square` - The square.
+    ///
+    /// # Returns
+    ///
+    /// The piece on the square, or `None` if the square is empty.
+    ///
+    fn piece_on(&self, square: Square) -> Option<Piece> {
+        self.mailbox[square.index() as usize]
+    }
+
+    ///
+    /// Adds a piece to or removes a piece from a square, updating the combined bitboards and
+    /// the Zobrist key.
+    ///
+    /// # Arguments
+    ///
+    /// * `piece` - The piece to add or remove.
+    /// * `square` - The square.
+    ///
+    fn toggle(&mut self, piece: Piece, square: Square) {
+        let bit = 1u64 << square.index();
+
+        match piece {
+            Piece::WhitePawn => self.white_pawns ^= bit,
+            Piece::WhiteKnight => self.white_knights ^= bit,
+            Piece::WhiteBishop => self.white_bishops ^= bit,
+            Piece::WhiteRook => self.white_rooks ^= bit,
+            Piece::WhiteQueen => self.white_queens ^= bit,
+            Piece::WhiteKing => self.white_king ^= bit,
+            Piece::BlackPawn => self.black_pawns ^= bit,
+            Piece::BlackKnight => self.black_knights ^= bit,
+            Piece::BlackBishop => self.black_bishops ^= bit,
+            Piece::BlackRook => self.black_rooks ^= bit,
+            Piece::BlackQueen => self.black_queens ^= bit,
+            Piece::BlackKing => self.black_king ^= bit,
+        }
+
+        match piece {
+            Piece::WhiteKing | Piece::BlackKing => {}
+            Piece::WhitePawn
+            | Piece::WhiteKnight
+            | Piece::WhiteBishop
+            | Piece::WhiteRook
+            | Piece::WhiteQueen => self.white_pieces ^= bit,
+            _ => self.black_pieces ^= bit,
+        }
+
+        self.empty_squares ^= bit;
+        self.hash ^= zobrist::PIECES[piece as usize][square.index() as usize];
+        self.mailbox[square.index() as usize] = if self.empty_squares & bit == 0 {
+            Some(piece)
+        } else {
+            None
+        };
+    }
+
+    ///
+    /// Modifies and returns the board with the given move applied.
+    ///
+    /// # Arguments
+    ///
+    /// * `m` - The move to be applied.
+    /// * `board_type` - The type of piece's board to be modified.
+    ///
+    /// # Returns
+    ///
+    /// The modified board.
+    ///
+    fn move_board(&self, m: &Move, board_type: Piece) -> u64 {
+        // get respective bitboard
+        let board = match board_type {
+            Piece::WhitePawn => self.white_pawns,
+            Piece::WhiteKnight => self.white_knights,
+            Piece::WhiteBishop => self.white_bishops,
+            Piece::WhiteRook => self.white_rooks,
+            Piece::WhiteQueen => self.white_queens,
+            Piece::WhiteKing => self.white_king,
+
+            Piece::BlackPawn => self.black_pawns,
+            Piece::BlackKnight => self.black_knights,
+            Piece::BlackBishop => self.black_bishops,
+            Piece::BlackRook => self.black_rooks,
+            Piece::BlackQueen => self.black_queens,
+            Piece::BlackKing => self.black_king,
+        };
+
+        // make move
+        match m {
+            Move::Normal { from, to } => {
+                if board & (1u64 << from.index()) == 0 {
+                    // not "from" piece; empty "to" position
+                    board & !(1u64 << to.index())
+                } else {
+                    // "from" piece; move from "from" to "to" position
+                    (board & !(1u64 << from.index())) | (1u64 << to.index())
+                }
+            }
+            Move::Castle { from, to, rook } => {
+                if board & (1u64 << from.index()) != 0 {
+                    // the king bitboard
+                    // move the king from "from" to "to" position
+                    return (board & !(1u64 << from.index())) | (1u64 << to.index());
+                }
+                // not king
+
+                if board & (1u64 << rook.index()) != 0 {
+                    // the rook bitboard
+                    // calulate new rook position
+                    let new_rook = if to > from {
+                        to.index() - 1
+                    } else {
+                        to.index() + 1
+                    };
+
+                    // move the rook from "rook" to "new_rook" position
+                    return (board & !(1u64 << rook.index())) | (1u64 << new_rook);
+                }
+
+                board
+            }
+            Move::EnPassant { from, to, captured } => {
+                if board & (1u64 << from.index()) != 0 {
+                    // the pawn bitboard
+                    // move the pawn from "from" to "to" position
+                    return (board & !(1u64 << from.index())) | 1u64 << to.index();
+                }
+
+                // not pawn
+                if board & (1u64 << captured.index()) != 0 {
+                    // the captured bitboard
+                    // remove the captured piece from the board
+                    return board & !(1u64 << captured.index());
+                }
+
+                board
+            }
+            Move::Promotion {
+                from,
+                to,
+                promotion,
+            } => {
+                if board & (1u64 << from.index()) != 0 {
+                    // the pawn bitboard
+                    // remove the pawn from the board
+                    return board & !(1u64 << from.index());
+                }
+
+                if promotion == &board_type {
+                    // the promoted piece bitboard
+                    // add the promoted piece to the board
+                    return board | (1u64 << to.index());
+                }
+
+                if board & (1u64 << to.index()) != 0 {
+                    // captured bitboard
+                    // remove the captured piece from the board
+                    return board & !(1u64 << to.index());
+                }
+
+                board
+            }
         }
     }
 
     ///
-    /// Modifies and returns the board with the given move applied.
+    /// Calculates the positions possibly moved to by a horizontal or vertical slider.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The position of the slider as a number between 0 and 63 (both included).
+    ///
+    /// # Returns
+    ///
+    /// A bitboard representing the possible positions.
+    ///
+    fn possible_hv(&self, position: u8) -> u64 {
+        Board::hv_attacks(position, !self.empty_squares)
+    }
+
+    ///
+    /// Calculates the positions possibly moved to by a diagonal or anti-diagonal slider.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The position of the slider as a number between 0 and 63 (both included).
+    ///
+    /// # Returns
+    ///
+    /// A bitboard representing the possible positions.
+    ///
+    fn possible_da(&self, position: u8) -> u64 {
+        Board::da_attacks(position, !self.empty_squares)
+    }
+
+    ///
+    /// Calculates the positions attacked by a horizontal or vertical slider.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The position of the slider as a number between 0 and 63 (both included).
+    /// * `occupied` - A bitboard representing all occupied squares.
+    ///
+    /// # Returns
+    ///
+    /// A bitboard representing the attacked positions.
+    ///
+    fn hv_attacks(position: u8, occupied: u64) -> u64 {
+        magic::rook_attacks(position, occupied)
+    }
+
+    ///
+    /// Calculates the positions attacked by a diagonal or anti-diagonal slider.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The position of the slider as a number between 0 and 63 (both included).
+    /// * `occupied` - A bitboard representing all occupied squares.
+    ///
+    /// # Returns
+    ///
+    /// A bitboard representing the attacked positions.
+    ///
+    fn da_attacks(position: u8, occupied: u64) -> u64 {
+        magic::bishop_attacks(position, occupied)
+    }
+
+    ///
+    /// Calculates the positions attacked by a knight.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The position of the knight as a number between 0 and 63 (both included).
+    ///
+    /// # Returns
+    ///
+    /// A bitboard representing the attacked positions.
+    ///
+    fn knight_attacks(position: u8) -> u64 {
+        KNIGHT_ATTACKS[position as usize]
+    }
+
+    ///
+    /// Calculates the positions attacked by a king.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The position of the king as a number between 0 and 63 (both included).
+    ///
+    /// # Returns
+    ///
+    /// A bitboard representing the attacked positions.
+    ///
+    fn king_attacks(position: u8) -> u64 {
+        KING_ATTACKS[position as usize]
+    }
+
+    ///
+    /// Calculates the positions attacked by pawns.
+    ///
+    /// # Arguments
+    ///
+    /// * `pawns` - A bitboard representing the pawns.
+    /// * `white` - Whether the pawns are white.
+    ///
+    /// # Returns
+    ///
+    /// A bitboard representing the attacked positions.
+    ///
+    fn pawn_attacks(pawns: u64, white: bool) -> u64 {
+        if white {
+            ((pawns << 7) & !FILE_H) | ((pawns << 9) & !FILE_A)
+        } else {
+            ((pawns >> 7) & !FILE_A) | ((pawns >> 9) & !FILE_H)
+        }
+    }
+
+    ///
+    /// Calculates the positions between two positions on the same rank, file or diagonal.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The first position as a number between 0 and 63 (both included).
+    /// * `b` - The second position as a number between 0 and 63 (both included).
+    ///
+    /// # Returns
+    ///
+    /// A bitboard representing the positions strictly between `a` and `b`, or 0 if they do not
+    /// share a rank, file or diagonal.
+    ///
+    fn squares_between(a: u8, b: u8) -> u64 {
+        let (rank_a, file_a) = (a / 8, a % 8);
+        let (rank_b, file_b) = (b / 8, b % 8);
+
+        if a == b {
+            0
+        } else if rank_a == rank_b || file_a == file_b {
+            Board::hv_attacks(a, 1u64 << b) & Board::hv_attacks(b, 1u64 << a)
+        } else if rank_a.abs_diff(rank_b) == file_a.abs_diff(file_b) {
+            Board::da_attacks(a, 1u64 << b) & Board::da_attacks(b, 1u64 << a)
+        } else {
+            0
+        }
+    }
+
+    ///
+    /// Get all positions attacked by a side.
+    ///
+    /// # Arguments
+    ///
+    /// * `white` - Whether the attacking side is white.
+    /// * `occupied` - A bitboard representing all occupied squares, blocking the sliders.
+    ///
+    /// # Returns
+    ///
+    /// A bitboard representing all attacked positions, including those occupied by pieces of the
+    /// attacking side.
+    ///
+    fn attacked_by(&self, white: bool, occupied: u64) -> u64 {
+        let side = self.side(white);
+        let bishop_queen = side.bishops | side.queens;
+        let rook_queen = side.rooks | side.queens;
+
+        let mut attacks = Board::pawn_attacks(side.pawns, white);
+
+        for i in SetBits(side.knights) {
+            attacks |= Board::knight_attacks(i);
+        }
+        for i in SetBits(bishop_queen) {
+            attacks |= Board::da_attacks(i, occupied);
+        }
+        for i in SetBits(rook_queen) {
+            attacks |= Board::hv_attacks(i, occupied);
+        }
+        for i in SetBits(side.king) {
+            attacks |= Board::king_attacks(i);
+        }
+
+        attacks
+    }
+
+    ///
+    /// Whether the king of the side to move is attacked.
+    ///
+    pub fn is_in_check(&self) -> bool {
+        let king = self.side(self.white_turn).king;
+
+        king & self.attacked_by(!self.white_turn, !self.empty_squares) != 0
+    }
+
+    ///
+    /// Get all pieces of a side attacking a position.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The attacked position as a number between 0 and 63 (both included).
+    /// * `white` - Whether the attacking side is white.
+    /// * `occupied` - A bitboard representing all occupied squares, blocking the sliders.
+    ///
+    /// # Returns
+    ///
+    /// A bitboard representing the attacking pieces.
+    ///
+    fn attackers_of(&self, position: u8, white: bool, occupied: u64) -> u64 {
+        let side = self.side(white);
+
+        // a pawn attacks the position if a pawn of the other color on the position attacks it
+        (PAWN_ATTACKS[white as usize][position as usize] & side.pawns)
+            | (Board::knight_attacks(position) & side.knights)
+            | (Board::da_attacks(position, occupied) & (side.bishops | side.queens))
+            | (Board::hv_attacks(position, occupied) & (side.rooks | side.queens))
+            | (Board::king_attacks(position) & side.king)
+    }
+
+    ///
+    /// Get the pieces giving check to the king of the side to move.
+    ///
+    /// # Returns
+    ///
+    /// A bitboard of the checking pieces, with two squares on a double check.
+    ///
+    pub fn checkers(&self) -> Bitboard {
+        let occupied = !self.empty_squares;
+        let mut checkers = 0;
+
+        for king in SetBits(self.side(self.white_turn).king) {
+            checkers |= self.attackers_of(king, !self.white_turn, occupied);
+        }
+
+        Bitboard(checkers)
+    }
+
+    ///
+    /// Get the pieces of a side attacking a square.
+    ///
+    /// # Arguments
+    ///
+    /// * `square` - The attacked square, which may be empty or hold a piece of either side.
+    /// * `color` - The color of the attacking side.
+    ///
+    /// # Returns
+    ///
+    /// A bitboard of the attacking pieces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use osprey::{Bitboard, Board, Color, Square};
+    ///
+    /// let board = Board::new("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+    ///
+    /// assert_eq!(
+    ///     board.attackers_to(Square::F3, Color::White),
+    ///     Bitboard::EMPTY | Square::E2 | Square::G2 | Square::G1
+    /// );
+    /// ```
+    ///
+    pub fn attackers_to(&self, square: Square, color: Color) -> Bitboard {
+        Bitboard(self.attackers_of(square.index(), color.is_white(), !self.empty_squares))
+    }
+
+    ///
+    /// Whether a square is attacked by a side.
+    ///
+    /// # Arguments
+    ///
+    /// * `square` - The attacked square, which may be empty or hold a piece of either side.
+    /// * `color` - The color of the attacking side.
+    ///
+    pub fn is_square_attacked(&self, square: Square, color: Color) -> bool {
+        !self.attackers_to(square, color).is_empty()
+    }
+
+    ///
+    /// Get the pieces of a side pinned to their king.
+    ///
+    /// A piece is pinned when it is the only piece between its king and an enemy bishop, rook or
+    /// queen attacking along that line, so moving it off the line would expose the king.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The color of the pinned pieces.
+    ///
+    /// # Returns
+    ///
+    /// A bitboard of the pinned pieces.
+    ///
+    pub fn pinned_pieces(&self, color: Color) -> Bitboard {
+        let white = color.is_white();
+        let (own, enemy) = (self.side(white), self.side(!white));
+        let occupied = !self.empty_squares;
+        let mut pinned = 0;
+
+        for king in SetBits(own.king) {
+            // sliders attacking the king when looking through its own pieces
+            let pinners = (Board::hv_attacks(king, occupied & !own.all())
+                & (enemy.rooks | enemy.queens))
+                | (Board::da_attacks(king, occupied & !own.all()) & (enemy.bishops | enemy.queens));
+
+            for i in SetBits(pinners) {
+                let blockers = Board::squares_between(king, i) & occupied;
+
+                if blockers.count_ones() == 1 {
+                    pinned |= blockers & own.pieces;
+                }
+            }
+        }
+
+        Bitboard(pinned)
+    }
+
+    ///
+    /// Get the squares between two squares on the same rank, file or diagonal.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The first square.
+    /// * `b` - The second square.
+    ///
+    /// # Returns
+    ///
+    /// A bitboard of the squares strictly between `a` and `b`, or an empty bitboard if they do
+    /// not share a rank, file or diagonal.
+    ///
+    pub fn between(a: Square, b: Square) -> Bitboard {
+        Bitboard(Board::squares_between(a.index(), b.index()))
+    }
+
+    ///
+    /// Get the whole line through two squares on the same rank, file or diagonal.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The first square.
+    /// * `b` - The second square.
+    ///
+    /// # Returns
+    ///
+    /// A bitboard of the squares of the line from one edge of the board to the other, including
+    /// `a` and `b`, or an empty bitboard if they are the same square or do not share a rank, file
+    /// or diagonal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use osprey::{Board, Square};
+    ///
+    /// let line = Board::line(Square::C3, Square::E5);
+    ///
+    /// assert_eq!(line.count(), 8);
+    /// assert!(line.contains(Square::A1) && line.contains(Square::H8));
+    /// ```
+    ///
+    pub fn line(a: Square, b: Square) -> Bitboard {
+        let (a, b) = (a.index(), b.index());
+        let (rank_a, file_a) = (a / 8, a % 8);
+        let (rank_b, file_b) = (b / 8, b % 8);
+
+        // the lines through `a` and `b` only share the line through both of them
+        let line = if a == b {
+            return Bitboard::EMPTY;
+        } else if rank_a == rank_b || file_a == file_b {
+            Board::hv_attacks(a, 0) & Board::hv_attacks(b, 0)
+        } else if rank_a.abs_diff(rank_b) == file_a.abs_diff(file_b) {
+            Board::da_attacks(a, 0) & Board::da_attacks(b, 0)
+        } else {
+            return Bitboard::EMPTY;
+        };
+
+        Bitboard(line | 1u64 << a | 1u64 << b)
+    }
+
+    ///
+    /// Evaluates the exchange of pieces on the target square of a move.
+    ///
+    /// Starting with the move, both sides take turns capturing on the target square with their
+    /// least valuable attacker, where each side may stop capturing when that is better for it.
+    /// Sliders behind the capturing pieces join the exchange once those pieces have left their
+    /// line, and pawns reaching the last rank promote to a queen. Pins and checks are ignored.
     ///
     /// # Arguments
     ///
-    /// * `m` - The move to be applied.
-    /// * `board_type` - The type of piece's board to be modified.
+    /// * `m` - The move, which should be legal in the position.
     ///
     /// # Returns
     ///
-    /// The modified board.
+    /// The material won by the side to move in centipawns, with 100 for a pawn, 300 for a knight
+    /// or bishop, 500 for a rook and 900 for a queen. Castling is always worth 0.
     ///
-    fn move_board(&self, m: &Move, board_type: Piece) -> u64 {
-        // get respective bitboard
-        let board = match board_type {
-            Piece::WhitePawn => self.white_pawns,
-            Piece::WhiteKnight => self.white_knights,
-            Piece::WhiteBishop => self.white_bishops,
-            Piece::WhiteRook => self.white_rooks,
-            Piece::WhiteQueen => self.white_queens,
-            Piece::WhiteKing => self.white_king,
-
-            Piece::BlackPawn => self.black_pawns,
-            Piece::BlackKnight => self.black_knights,
-            Piece::BlackBishop => self.black_bishops,
-            Piece::BlackRook => self.black_rooks,
-            Piece::BlackQueen => self.black_queens,
-            Piece::BlackKing => self.black_king,
+    /// # Examples
+    ///
+    /// ```
+    /// use osprey::Board;
+    ///
+    /// // the knight wins a pawn defended by nothing
+    /// let board = Board::new("4k3/8/8/3p4/8/4N3/8/4K3 w - - 0 1");
+    /// assert_eq!(board.see(board.parse_san("Nxd5").unwrap()), 100);
+    ///
+    /// // but loses itself for a pawn when a pawn defends it
+    /// let board = Board::new("4k3/8/4p3/3p4/8/4N3/8/4K3 w - - 0 1");
+    /// assert_eq!(board.see(board.parse_san("Nxd5").unwrap()), -200);
+    /// ```
+    ///
+    pub fn see(&self, m: Move) -> i32 {
+        let value = |piece: Piece| SEE_VALUES[piece as usize % 6];
+        let pawn = SEE_VALUES[0];
+        let queen = SEE_VALUES[4];
+
+        let (from, to) = (m.from_square(), m.to_square());
+        let mut occupied = !self.empty_squares & !(1u64 << from.index());
+
+        // the value of the first capture and of the piece left on the target square
+        let (gain, mut on_square) = match m {
+            Move::Castle { .. } => return 0,
+            Move::Normal { .. } => {
+                let moved = self.piece_on(from).map_or(0, value);
+                (self.piece_on(to).map_or(0, value), moved)
+            }
+            Move::EnPassant { captured, .. } => {
+                occupied &= !(1u64 << captured.index());
+                (pawn, pawn)
+            }
+            Move::Promotion { promotion, .. } => (
+                self.piece_on(to).map_or(0, value) + value(promotion) - pawn,
+                value(promotion),
+            ),
         };
 
-        // make move
-        match m {
-            Move::Normal { from, to } => {
-                if board & (1u64 << from) == 0 {
-                    // not "from" piece; empty "to" position
-                    return board & !(1u64 << to);
-                } else {
-                    // "from" piece; move from "from" to "to" position
-                    return (board & !(1u64 << from)) | (1u64 << to);
-                }
-            }
-            Move::Castle { from, to, rook } => {
-                if board & (1u64 << from) != 0 {
-                    // the king bitboard
-                    // move the king from "from" to "to" position
-                    return (board & !(1u64 << from)) | (1u64 << to);
-                }
-                // not king
+        // the gains of the captures, each being the material won by the capturing side if the
+        // exchange stops after it
+        let mut gains = [0i32; 32];
+        let mut depth = 0;
+        let mut white = !self.white_turn;
+        // only pawns that promote on the target square can attack it on the first or last rank
+        let promotes = (RANK_1 | RANK_8) & (1u64 << to.index()) != 0;
 
-                if board & (1u64 << rook) != 0 {
-                    // the rook bitboard
-                    // calulate new rook position
-                    let new_rook = if to > from { to - 1 } else { to + 1 };
+        gains[0] = gain;
 
-                    // move the rook from "rook" to "new_rook" position
-                    return (board & !(1u64 << rook)) | (1u64 << new_rook);
-                }
+        loop {
+            let attackers = self.attackers_of(to.index(), white, occupied) & occupied;
 
-                board
+            if attackers == 0 || depth + 1 == gains.len() {
+                break;
             }
-            Move::EnPassant { from, to, captured } => {
-                if board & (1u64 << from) != 0 {
-                    // the pawn bitboard
-                    // move the pawn from "from" to "to" position
-                    return (board & !(1u64 << from)) | 1u64 << to;
-                }
 
-                // not pawn
-                if board & (1u64 << captured) != 0 {
-                    // the captured bitboard
-                    // remove the captured piece from the board
-                    return board & !(1u64 << captured);
-                }
-
-                board
+            let side = self.side(white);
+            let (attacker, mut attacker_value) = [
+                side.pawns,
+                side.knights,
+                side.bishops,
+                side.rooks,
+                side.queens,
+                side.king,
+            ]
+            .iter()
+            .zip(SEE_VALUES)
+            .find(|(pieces, _)| **pieces & attackers != 0)
+            .map(|(pieces, value)| (*pieces & attackers, value))
+            .unwrap();
+
+            depth += 1;
+            gains[depth] = on_square - gains[depth - 1];
+
+            if attacker_value == pawn && promotes {
+                gains[depth] += queen - pawn;
+                attacker_value = queen;
             }
-            Move::Promotion {
-                from,
-                to,
-                promotion,
-            } => {
-                if board & (1u64 << from) != 0 {
-                    // the pawn bitboard
-                    // remove the pawn from the board
-                    return board & !(1u64 << from);
-                }
-
-                if promotion == &board_type {
-                    // the promoted piece bitboard
-                    // add the promoted piece to the board
-                    return board | (1u64 << to);
-                }
 
-                if board & (1u64 << to) != 0 {
-                    // captured bitboard
-                    // remove the captured piece from the board
-                    return board & !(1u64 << to);
-                }
+            on_square = attacker_value;
+            occupied &= !(1u64 << attacker.trailing_zeros());
+            white = !white;
+        }
 
-                board
-            }
+        // each side only captures when the capture does not lose material
+        while depth > 0 {
+            gains[depth - 1] = -(-gains[depth - 1]).max(gains[depth]);
+            depth -= 1;
         }
+
+        gains[0]
     }
 
     ///
-    /// Calculates the positions possibly moved to by a horizontal or vertical slider.
+    /// Whether the exchange of pieces on the target square of a move wins at least a threshold,
+    /// as given by `Board::see`.
     ///
     /// # Arguments
     ///
-    /// * `position` - The position of the slider as a number between 0 and 63 (both included).
+    /// * `m` - The move, which should be legal in the position.
+    /// * `threshold` - The least material in centipawns the side to move should win.
     ///
-    /// # Returns
+    pub fn see_ge(&self, m: Move, threshold: i32) -> bool {
+        self.see(m) >= threshold
+    }
+
     ///
-    /// A bitboard representing the possible positions.
+    /// Whether a move captures a piece.
     ///
-    fn possible_hv(&self, position: u8) -> u64 {
-        let slider = 1u64 << position;
-        let occupied = !self.empty_squares;
-
-        let horizontal = (occupied.wrapping_sub(2u64.wrapping_mul(slider)))
-            ^ (occupied
-                .reverse_bits()
-                .wrapping_sub(2u64.wrapping_mul(slider.reverse_bits())))
-            .reverse_bits();
-        let vertical = ((occupied & FILES[position as usize % 8])
-            .wrapping_sub(2u64.wrapping_mul(slider)))
-            ^ ((occupied & FILES[position as usize % 8])
-                .reverse_bits()
-                .wrapping_sub(2u64.wrapping_mul(slider.reverse_bits())))
-            .reverse_bits();
+    /// # Arguments
+    ///
+    /// * `m` - The move, which should be legal in the position.
+    ///
+    pub fn is_capture(&self, m: Move) -> bool {
+        match m {
+            Move::Normal { to, .. } | Move::Promotion { to, .. } => self.piece_on(to).is_some(),
+            Move::EnPassant { .. } => true,
+            Move::Castle { .. } => false,
+        }
+    }
 
-        (horizontal & RANKS[(position / 8) as usize]) | (vertical & FILES[(position % 8) as usize])
+    ///
+    /// Whether a move checks the king of the other side.
+    ///
+    /// # Arguments
+    ///
+    /// * `m` - The move, which should be legal in the position.
+    ///
+    pub fn gives_check(&self, m: Move) -> bool {
+        let mut board = *self;
+        board.make(m);
+        board.is_in_check()
     }
 
     ///
-    /// Calculates the positions possibly moved to by a diagonal or anti-diagonal slider.
+    /// Get a subset of the legal moves the side to move can make.
+    ///
+    /// The captures and the quiet moves together are all legal moves, without any move in both.
+    /// Each kind is generated on its own: the captures only to the squares of the other side and
+    /// the last rank, the quiet moves only to empty squares, and the quiet checks only to the
+    /// squares attacking the other king or by the pieces that can discover a check.
     ///
     /// # Arguments
     ///
-    /// * `position` - The position of the slider as a number between 0 and 63 (both included).
+    /// * `kind` - The kind of moves to generate.
     ///
     /// # Returns
     ///
-    /// A bitboard representing the possible positions.
+    /// A list of the legal moves of the kind.
     ///
-    fn possible_da(&self, position: u8) -> u64 {
-        let slider = 1u64 << position;
-        let occupied = !self.empty_squares;
-
-        let diagonal = (occupied & DIAGONALS[position as usize / 8 + position as usize % 8])
-            .wrapping_sub(2u64.wrapping_mul(slider))
-            ^ (occupied & DIAGONALS[position as usize / 8 + position as usize % 8])
-                .reverse_bits()
-                .wrapping_sub(2u64.wrapping_mul(slider.reverse_bits()))
-                .reverse_bits();
-        let anti_diagonal = (occupied
-            & ANTI_DIAGONALS[position as usize / 8 + 7 - position as usize % 8])
-            .wrapping_sub(2u64.wrapping_mul(slider))
-            ^ (occupied & ANTI_DIAGONALS[position as usize / 8 + 7 - position as usize % 8])
-                .reverse_bits()
-                .wrapping_sub(2u64.wrapping_mul(slider.reverse_bits()))
-                .reverse_bits();
-
-        (diagonal & DIAGONALS[position as usize / 8 + position as usize % 8])
-            | (anti_diagonal & ANTI_DIAGONALS[position as usize / 8 + 7 - position as usize % 8])
-    }
-
+    /// # Examples
     ///
-    /// Get all pseudo-legal moves (without worrying about check) white can make.
+    /// ```
+    /// use osprey::{Board, MoveKind};
     ///
-    /// # Returns
+    /// let board = Board::new("4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1");
+    /// let captures = board.generate(MoveKind::Captures);
     ///
-    /// A vector of all pseudo-legal moves white can make.
+    /// assert_eq!(captures.len(), 1);
+    /// assert_eq!(board.to_san(captures[0]), "exd5");
+    /// assert_eq!(board.generate(MoveKind::Quiets).len(), 6);
+    /// ```
     ///
-    pub fn possible_white(&self) -> Vec<Move> {
-        let mut moves: Vec<Move> = Vec::new();
+    pub fn generate(&self, kind: MoveKind) -> MoveList {
+        let mut moves = MoveList::new();
+        let white = self.white_turn;
 
-        moves.append(&mut self.possible_wp());
-        moves.append(&mut self.possible_wk());
-        moves.append(&mut self.possible_wq());
-        moves.append(&mut self.possible_wr());
-        moves.append(&mut self.possible_wb());
-        moves.append(&mut self.possible_wn());
-        moves.append(&mut self.possible_wc());
+        match kind {
+            MoveKind::Captures | MoveKind::Quiets => {
+                let captures = kind == MoveKind::Captures;
+
+                if white {
+                    self.possible::<true>(&mut moves, captures, !captures);
+                } else {
+                    self.possible::<false>(&mut moves, captures, !captures);
+                }
+            }
+            MoveKind::QuietChecks => {
+                if white {
+                    self.quiet_checks::<true>(&mut moves);
+                } else {
+                    self.quiet_checks::<false>(&mut moves);
+                }
+            }
+            MoveKind::Evasions => {
+                if self.is_in_check() {
+                    moves = self.possible_moves(self.turn());
+                }
+            }
+        }
 
+        self.retain_legal(&mut moves);
         moves
     }
 
     ///
-    /// Get all pseudo-legal moves (without worrying about check) black can make.
+    /// Get all strictly legal moves the side to move can make.
+    ///
+    /// The moves are generated by computing the checking pieces and the pinned pieces up front,
+    /// so no move has to be made to test whether it leaves the king in check.
     ///
     /// # Returns
     ///
-    /// A vector of all pseudo-legal moves black can make.
+    /// A list of all legal moves.
     ///
-    pub fn possible_black(&self) -> Vec<Move> {
-        let mut moves: Vec<Move> = Vec::new();
-
-        moves.append(&mut self.possible_bp());
-        moves.append(&mut self.possible_bk());
-        moves.append(&mut self.possible_bq());
-        moves.append(&mut self.possible_br());
-        moves.append(&mut self.possible_bb());
-        moves.append(&mut self.possible_bn());
-        moves.append(&mut self.possible_bc());
-
+    pub fn legal_moves(&self) -> MoveList {
+        let mut moves = self.possible_moves(self.turn());
+        self.retain_legal(&mut moves);
         moves
     }
 
     ///
-    /// Get all pseudo-legal pawn moves white can make.
+    /// Removes the moves that leave the king of the side to move in check.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// A vector of all pseudo-legal pawn moves white can make.
+    /// * `moves` - The pseudo-legal moves of the side to move.
     ///
-    fn possible_wp(&self) -> Vec<Move> {
-
-        let mut moves: Vec<Move> = Vec::new();
+    fn retain_legal(&self, moves: &mut MoveList) {
+        let white = self.white_turn;
+        let (own, enemy) = (self.side(white), self.side(!white));
+        let king = own.king;
+        let enemy_bishop_queen = enemy.bishops | enemy.queens;
+        let enemy_rook_queen = enemy.rooks | enemy.queens;
+
+        // without exactly one king there are no checks or pins to compute
+        if king.count_ones() != 1 {
+            moves.retain(|m| self.make_move(m).is_ok());
+            return;
+        }
 
-        // Pawn NE captures
+        let king_position = king.trailing_zeros() as u8;
+        let occupied = !self.empty_squares;
 
-        let mut pawn_moves =
-            (self.white_pawns << 9) & !FILE_A & self.black_pieces & !RANK_1 & !RANK_8;
+        // squares the king can not move to, with the king removed so it can not step back along
+        // the ray of a checking slider
+        let danger = self.attacked_by(!white, occupied & !king);
 
-        for i in 0..64 {
-            if pawn_moves & (1u64 << i) != 0 {
-                moves.push(Move::Normal { from: i - 9, to: i });
-            }
-        }
+        // squares that block or capture a single checking piece
+        let checkers = self.attackers_of(king_position, !white, occupied);
+        let check_mask = match checkers.count_ones() {
+            0 => !0u64,
+            1 => checkers | Board::squares_between(king_position, checkers.trailing_zeros() as u8),
+            _ => 0,
+        };
 
-        // Pawn NW captures
+        // squares a pinned piece can move to, being the ray between the king and the pinner
+        let mut pin_rays = [!0u64; 64];
+        let pinners = (Board::hv_attacks(king_position, occupied & !own.all()) & enemy_rook_queen)
+            | (Board::da_attacks(king_position, occupied & !own.all()) & enemy_bishop_queen);
 
-        pawn_moves = (self.white_pawns << 7) & !FILE_H & self.black_pieces & !RANK_1 & !RANK_8;
+        for i in SetBits(pinners) {
+            let between = Board::squares_between(king_position, i);
+            let blockers = between & occupied;
 
-        for i in 0..64 {
-            if pawn_moves & (1u64 << i) != 0 {
-                moves.push(Move::Normal { from: i - 7, to: i });
+            if blockers.count_ones() == 1 && blockers & own.pieces != 0 {
+                pin_rays[blockers.trailing_zeros() as usize] = between | (1u64 << i);
             }
         }
 
-        // Pawn forward one
+        moves.retain(|m| match *m {
+            Move::Normal { from, to } if from.index() == king_position => {
+                danger & (1u64 << to.index()) == 0
+            }
+            Move::Normal { from, to } | Move::Promotion { from, to, .. } => {
+                check_mask & pin_rays[from.index() as usize] & (1u64 << to.index()) != 0
+            }
+            // castling already checks the king, the passed and the destination squares
+            Move::Castle { .. } => true,
+            Move::EnPassant { from, to, captured } => {
+                if (check_mask & (1u64 << captured.index() | 1u64 << to.index())) == 0
+                    || pin_rays[from.index() as usize] & (1u64 << to.index()) == 0
+                {
+                    return false;
+                }
 
-        pawn_moves = (self.white_pawns << 8) & self.empty_squares & !RANK_1 & !RANK_8;
+                // removing both pawns from the rank can expose the king to a slider
+                let occupied = (occupied & !(1u64 << from.index() | 1u64 << captured.index()))
+                    | 1u64 << to.index();
 
-        for i in 0..64 {
-            if pawn_moves & (1u64 << i) != 0 {
-                moves.push(Move::Normal { from: i - 8, to: i });
+                Board::hv_attacks(king_position, occupied) & enemy_rook_queen == 0
+                    && Board::da_attacks(king_position, occupied) & enemy_bishop_queen == 0
             }
-        }
-
-        // Pawn forward two
+        });
+    }
 
-        pawn_moves = (pawn_moves << 8)
-            & ((self.white_pawns & RANK_2) << 16 & self.empty_squares & !RANK_1 & !RANK_2);
+    ///
+    /// Whether neither side has the pieces left to checkmate the other, whatever moves are made.
+    ///
+    /// This is the case with only kings and at most one knight or bishop left, or with only kings
+    /// and bishops left that all stand on squares of the same color.
+    ///
+    pub fn is_insufficient_material(&self) -> bool {
+        let (white, black) = (self.side(true), self.side(false));
+        let pawns_and_majors = |side: Side| side.pawns | side.rooks | side.queens;
 
-        for i in 0..64 {
-            if pawn_moves & (1u64 << i) != 0 {
-                moves.push(Move::Normal {
-                    from: i - 16,
-                    to: i,
-                });
-            }
+        if pawns_and_majors(white) | pawns_and_majors(black) != 0 {
+            return false;
         }
 
-        // Pawn Promotion
+        let knights = white.knights | black.knights;
+        let bishops = white.bishops | black.bishops;
 
-        pawn_moves = (self.white_pawns << 8) & self.empty_squares & RANK_8;
+        (knights | bishops).count_ones() <= 1
+            || (knights == 0 && (bishops & DARK_SQUARES == 0 || bishops & !DARK_SQUARES == 0))
+    }
 
-        for i in 0..64 {
-            if pawn_moves & (1u64 << i) != 0 {
-                for promotion in [Piece::WhiteBishop, Piece::WhiteKnight, Piece::WhiteRook, Piece::WhiteQueen].iter() {
-                    moves.push(Move::Promotion {
-                        from: i - 8,
-                        to: i,
-                        promotion: *promotion,
-                    });
+    ///
+    /// Get the status of the game in the position, not knowing the moves that led to it.
+    ///
+    /// Use `Game::status` to also detect repetitions.
+    ///
+    /// # Returns
+    ///
+    /// The status, being over on checkmate, stalemate, insufficient material or the
+    /// seventy-five-move rule, claimable on the fifty-move rule, and ongoing otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use osprey::{Board, Color, GameResult, Status, Termination};
+    ///
+    /// let board = Board::new("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3");
+    /// let outcome = board.status().outcome().unwrap();
+    ///
+    /// assert_eq!(outcome.winner, Some(Color::Black));
+    /// assert_eq!(outcome.termination, Termination::Checkmate);
+    /// assert_eq!(outcome.result(), GameResult::BlackWins);
+    /// ```
+    ///
+    pub fn status(&self) -> Status {
+        if self.legal_moves().is_empty() {
+            return Status::Over(if self.is_in_check() {
+                Outcome {
+                    winner: Some(!self.turn()),
+                    termination: Termination::Checkmate,
                 }
-            }
+            } else {
+                Outcome::draw(Termination::Stalemate)
+            });
         }
 
-        // Pawn Promotion NE captures
-
-        pawn_moves = (self.white_pawns << 9) & !FILE_A & self.black_pieces & RANK_8;
-
-        for i in 0..64 {
-            if pawn_moves & (1u64 << i) != 0 {
-                for promotion in [Piece::WhiteBishop, Piece::WhiteKnight, Piece::WhiteRook, Piece::WhiteQueen].iter() {
-                    moves.push(Move::Promotion {
-                        from: i - 9,
-                        to: i,
-                        promotion: *promotion,
-                    });
-                }
-            }
+        if self.is_insufficient_material() {
+            Status::Over(Outcome::draw(Termination::InsufficientMaterial))
+        } else if self.halfmove >= 150 {
+            Status::Over(Outcome::draw(Termination::SeventyFiveMoves))
+        } else if self.halfmove >= 100 {
+            Status::Claimable(Outcome::draw(Termination::FiftyMoves))
+        } else {
+            Status::Ongoing
         }
+    }
 
-        // Pawn Promotion NW captures
+    ///
+    /// Parses a move in the long algebraic notation of the UCI protocol.
+    ///
+    /// The move is resolved against the position, so `e1g1` becomes a castling move when the king
+    /// castles and a pawn capturing on the en passant square becomes an en passant move.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The move, as in `e2e4`, `e1g1` or `e7e8q`.
+    ///
+    /// # Returns
+    ///
+    /// The legal move, or an error if the string is not a UCI move or the move is not legal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use osprey::{Board, Move, Square};
+    ///
+    /// let board = Board::new("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+    ///
+    /// assert_eq!(
+    ///     board.parse_uci_move("e2e4"),
+    ///     Ok(Move::Normal {
+    ///         from: Square::E2,
+    ///         to: Square::E4,
+    ///     })
+    /// );
+    /// ```
+    ///
+    pub fn parse_uci_move(&self, input: &str) -> Result<Move, MoveParseError> {
+        let malformed = || MoveParseError::Malformed(input.to_string());
+
+        let square = |range| {
+            input
+                .get(range)
+                .and_then(|name: &str| name.parse::<Square>().ok())
+                .ok_or_else(malformed)
+        };
 
-        pawn_moves = (self.white_pawns << 7) & !FILE_H & self.black_pieces & RANK_8;
+        let (from, to) = (square(0..2)?, square(2..4)?);
 
-        for i in 0..64 {
-            if pawn_moves & (1u64 << i) != 0 {
-                for promotion in [Piece::WhiteBishop, Piece::WhiteKnight, Piece::WhiteRook, Piece::WhiteQueen].iter() {
-                    moves.push(Move::Promotion {
-                        from: i - 7,
-                        to: i,
-                        promotion: *promotion,
-                    });
-                }
-            }
-        }
+        let promotion = match &input.as_bytes()[4..] {
+            [] => None,
+            [letter @ (b'n' | b'b' | b'r' | b'q')] => Some(*letter as char),
+            _ => return Err(malformed()),
+        };
 
-        match self.en_passant {
-            Some(en_passant) => {
+        self.legal_moves()
+            .into_iter()
+            .find(|m| {
+                let promoted = match m {
+                    Move::Promotion { promotion, .. } => {
+                        Some(promotion.to_char().to_ascii_lowercase())
+                    }
+                    _ => None,
+                };
+
+                m.from_square() == from && m.to_square() == to && promoted == promotion
+            })
+            .ok_or_else(|| MoveParseError::Illegal(input.to_string()))
+    }
 
-                // Pawn NE en passant
+    ///
+    /// Get a legal move in Standard Algebraic Notation.
+    ///
+    /// The move is written with the letter of the moved piece (none for pawns), the file and/or
+    /// rank of the moved piece when another piece of the same type can move to the same square,
+    /// `x` for a capture, the square moved to, `=` and the letter of a promoted piece and `+` or
+    /// `#` when the move gives check or checkmate. Castling is written as `O-O` or `O-O-O`.
+    ///
+    /// # Arguments
+    ///
+    /// * `m` - The move, which should be legal on the board.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use osprey::{Board, Move, Square};
+    ///
+    /// let board = Board::new("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+    /// let m = Move::Normal {
+    ///     from: Square::G1,
+    ///     to: Square::F3,
+    /// };
+    ///
+    /// assert_eq!(board.to_san(m), "Nf3");
+    /// ```
+    ///
+    pub fn to_san(&self, m: Move) -> String {
+        let mut san = String::new();
+        let (from, to) = (m.from_square(), m.to_square());
 
-                pawn_moves = (self.white_pawns << 9) & !FILE_A & !RANK_1 & (1u64 << en_passant);
+        match m {
+            Move::Castle { .. } if to > from => san.push_str("O-O"),
+            Move::Castle { .. } => san.push_str("O-O-O"),
+            _ => {
+                let piece = self.piece_on(from);
+                let capture = self.piece_on(to).is_some() || matches!(m, Move::EnPassant { .. });
+
+                if matches!(piece, Some(Piece::WhitePawn | Piece::BlackPawn)) {
+                    if capture {
+                        san.push(from.file().char());
+                    }
+                } else {
+                    if let Some(piece) = piece {
+                        san.push(piece.to_char().to_ascii_uppercase());
+                    }
 
-                if pawn_moves != 0 && self.white_turn {
-                    moves.push(Move::EnPassant {
-                        from: en_passant - 9,
-                        to: en_passant,
-                        captured: en_passant - 8,
-                    });
+                    // the other pieces of the same type that can move to the same square
+                    let others: Vec<Square> = self
+                        .legal_moves()
+                        .into_iter()
+                        .filter(|other| {
+                            other.to_square() == to
+                                && other.from_square() != from
+                                && !matches!(other, Move::Castle { .. })
+                                && self.piece_on(other.from_square()) == piece
+                        })
+                        .map(|other| other.from_square())
+                        .collect();
+
+                    let same_file = others.iter().any(|other| other.file() == from.file());
+                    let same_rank = others.iter().any(|other| other.rank() == from.rank());
+
+                    if !others.is_empty() && (!same_file || same_rank) {
+                        san.push(from.file().char());
+                    }
+                    if same_file {
+                        san.push(from.rank().char());
+                    }
                 }
 
-                // Pawn NW en passant
+                if capture {
+                    san.push('x');
+                }
 
-                pawn_moves = (self.white_pawns << 7) & !FILE_H & !RANK_1 & (1u64 << en_passant);
+                san.push_str(&to.to_string());
 
-                if pawn_moves != 0 && self.white_turn {
-                    moves.push(Move::EnPassant {
-                        from: en_passant - 7,
-                        to: en_passant,
-                        captured: en_passant - 8,
-                    });
+                if let Move::Promotion { promotion, .. } = m {
+                    san.push('=');
+                    san.push(promotion.to_char().to_ascii_uppercase());
                 }
             }
-            None => {}
         }
 
-        moves
+        let mut board = *self;
+        board.make(m);
+
+        if board.is_in_check() {
+            san.push(if board.legal_moves().is_empty() {
+                '#'
+            } else {
+                '+'
+            });
+        }
+
+        san
     }
 
     ///
-    /// Get all pseudo-legal knight moves white can make.
+    /// Parses a move in Standard Algebraic Notation.
+    ///
+    /// Common sloppy forms are accepted as well: castling with zeros (`0-0`), a missing or
+    /// superfluous disambiguation (`Ng1f3`), hyphens and colons (`Ng1-f3`, `e4:d5`), a promotion
+    /// without `=` or with a lowercase letter (`e8Q`, `e8=q`), an en passant suffix (`e.p.`) and
+    /// missing or wrong check and annotation suffixes (`+`, `#`, `!`, `?`).
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The move, as in `e4`, `Nbd7`, `exd5`, `e8=Q+` or `O-O`.
     ///
     /// # Returns
     ///
-    /// A vector of all pseudo-legal knight moves white can make.
-    fn possible_wn(&self) -> Vec<Move> {
+    /// The legal move, or an error if the string is not a SAN move, the move is not legal or more
+    /// than one legal move matches it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use osprey::{Board, Move, Square};
+    ///
+    /// let board = Board::new("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+    ///
+    /// assert_eq!(
+    ///     board.parse_san("Nf3"),
+    ///     Ok(Move::Normal {
+    ///         from: Square::G1,
+    ///         to: Square::F3,
+    ///     })
+    /// );
+    /// ```
+    ///
+    pub fn parse_san(&self, input: &str) -> Result<Move, MoveParseError> {
+        let malformed = || MoveParseError::Malformed(input.to_string());
+        let san = input.trim_end_matches(['+', '#', '!', '?']);
+        let san = san.strip_suffix("e.p.").unwrap_or(san).trim_end();
 
-        let mut moves: Vec<Move> = Vec::new();
+        // castling
+        let castle = match san {
+            "O-O" | "0-0" => Some(true),
+            "O-O-O" | "0-0-0" => Some(false),
+            _ => None,
+        };
 
-        for i in 0..64 {
-            if self.white_knights & 1u64 << i != 0 {
-                let mut possibility: u64;
+        if let Some(kingside) = castle {
+            return self
+                .legal_moves()
+                .into_iter()
+                .find(|m| match m {
+                    Move::Castle { from, to, .. } => (to > from) == kingside,
+                    _ => false,
+                })
+                .ok_or_else(|| MoveParseError::Illegal(input.to_string()));
+        }
 
-                if i > 18 {
-                    possibility = KNIGHT_SPAN << (i - 18);
-                } else {
-                    possibility = KNIGHT_SPAN >> (18 - i);
-                }
+        let mut chars: Vec<char> = san
+            .chars()
+            .filter(|c| !matches!(c, 'x' | ':' | '-'))
+            .collect();
+
+        // the letter of the moved piece, none for a pawn
+        let piece = match chars.first() {
+            Some(letter @ ('N' | 'B' | 'R' | 'Q' | 'K')) => Some(*letter),
+            _ => None,
+        };
+        if piece.is_some() {
+            chars.remove(0);
+        }
+
+        // the letter of a promoted piece, following the rank of the square moved to
+        let promotion = match chars.as_slice() {
+            [.., '1'..='8', '=', letter] | [.., '1'..='8', letter] => match letter {
+                'N' | 'B' | 'R' | 'Q' | 'n' | 'b' | 'r' | 'q' => Some(letter.to_ascii_uppercase()),
+                _ => return Err(malformed()),
+            },
+            _ => None,
+        };
+        if promotion.is_some() {
+            chars.truncate(chars.iter().rposition(|c| c.is_ascii_digit()).unwrap_or(0) + 1);
+        }
+
+        // the square moved to, preceded by the file and/or rank of the moved piece
+        if chars.len() < 2 || chars.len() > 4 {
+            return Err(malformed());
+        }
+
+        let (hint, square) = chars.split_at(chars.len() - 2);
+        let to = match (File::from_char(square[0]), Rank::from_char(square[1])) {
+            (Some(file), Some(rank)) => Square::from_coords(file, rank),
+            _ => return Err(malformed()),
+        };
 
-                if i % 8 < 4 {
-                    possibility &= !(FILE_G | FILE_H) & !self.white_pieces & !self.white_king;
-                } else {
-                    possibility &= !(FILE_A | FILE_B) & !self.white_pieces & !self.white_king;
-                }
+        let (mut file, mut rank) = (None, None);
 
-                for j in 0..64 {
-                    if possibility & 1u64 << j != 0 {
-                        moves.push(Move::Normal { from: i, to: j });
-                    }
-                }
+        for c in hint {
+            match (File::from_char(*c), Rank::from_char(*c)) {
+                (Some(hint), _) if file.is_none() && rank.is_none() => file = Some(hint),
+                (_, Some(hint)) if rank.is_none() => rank = Some(hint),
+                _ => return Err(malformed()),
             }
         }
 
-        moves
+        let mut candidates = self.legal_moves();
+
+        candidates.retain(|m| {
+            let from = m.from_square();
+            let promoted = match m {
+                Move::Promotion { promotion, .. } => Some(promotion.to_char().to_ascii_uppercase()),
+                _ => None,
+            };
+            let letter = self
+                .piece_on(from)
+                .map(|piece| piece.to_char().to_ascii_uppercase());
+
+            m.to_square() == to
+                && letter == Some(piece.unwrap_or('P'))
+                && file.is_none_or(|file| from.file() == file)
+                && rank.is_none_or(|rank| from.rank() == rank)
+                && promoted == promotion
+        });
+
+        match candidates.as_slice() {
+            [m] => Ok(*m),
+            [] => Err(MoveParseError::Illegal(input.to_string())),
+            _ => Err(MoveParseError::Ambiguous(input.to_string())),
+        }
     }
 
     ///
-    /// Get all pseudo-legal bishop moves white can make.
+    /// Get all pseudo-legal moves (without worrying about check) white can make.
     ///
     /// # Returns
     ///
-    /// A vector of all pseudo-legal bishop moves white can make.
+    /// A list of all pseudo-legal moves white can make.
     ///
-    fn possible_wb(&self) -> Vec<Move> {
-        let mut moves: Vec<Move> = Vec::new();
-
-        for i in 0..64 {
-            if self.white_bishops & (1u64 << i) != 0 {
-                let bishop_moves = self.possible_da(i) & !self.white_pieces & !self.white_king;
-
-                for j in 0..64 {
-                    if bishop_moves & (1u64 << j) != 0 {
-                        moves.push(Move::Normal { from: i, to: j });
-                    }
-                }
-            }
-        }
-
-        moves
+    pub fn possible_white(&self) -> MoveList {
+        self.possible_moves(Color::White)
     }
 
     ///
-    /// Get all pseudo-legal rook moves white can make.
+    /// Get all pseudo-legal moves (without worrying about check) black can make.
     ///
     /// # Returns
     ///
-    /// A vector of all pseudo-legal rook moves white can make.
-    ///     
-    fn possible_wr(&self) -> Vec<Move> {
-        let mut moves: Vec<Move> = Vec::new();
-
-        for i in 0..64 {
-            if self.white_rooks & (1u64 << i) != 0 {
-                let rook_moves = self.possible_hv(i) & !self.white_pieces & !self.white_king;
-
-                for j in 0..64 {
-                    if rook_moves & (1u64 << j) != 0 {
-                        moves.push(Move::Normal { from: i, to: j });
-                    }
-                }
-            }
-        }
-
-        moves
+    /// A list of all pseudo-legal moves black can make.
+    ///
+    pub fn possible_black(&self) -> MoveList {
+        self.possible_moves(Color::Black)
     }
 
     ///
-    /// Get all pseudo-legal queen moves white can make.
+    /// Get all pseudo-legal moves (without worrying about check) a side can make.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The color of the side.
     ///
     /// # Returns
     ///
-    /// A vector of all pseudo-legal queen moves white can make.
+    /// A list of all pseudo-legal moves the side can make.
     ///
-    fn possible_wq(&self) -> Vec<Move> {
-        let mut moves: Vec<Move> = Vec::new();
+    pub fn possible_moves(&self, color: Color) -> MoveList {
+        let mut moves = MoveList::new();
 
-        for i in 0..64 {
-            if self.white_queens & (1u64 << i) != 0 {
-                let queen_moves = (self.possible_hv(i) | self.possible_da(i))
-                    & !self.white_pieces
-                    & !self.white_king;
-
-                for j in 0..64 {
-                    if queen_moves & (1u64 << j) != 0 {
-                        moves.push(Move::Normal { from: i, to: j });
-                    }
-                }
-            }
+        match color {
+            Color::White => self.possible::<true>(&mut moves, true, true),
+            Color::Black => self.possible::<false>(&mut moves, true, true),
         }
 
         moves
     }
 
     ///
-    /// Get all pseudo-legal king moves white can make.
+    /// Get the pseudo-legal captures or quiet moves a side can make, as split by `MoveKind`.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// A vector of all pseudo-legal king moves white can make.
+    /// * `WHITE` - Whether the side is white.
+    /// * `moves` - The list to add the pseudo-legal moves of the side to.
+    /// * `captures` - Whether to add the captures and the promotions to a queen.
+    /// * `quiets` - Whether to add the other moves.
     ///
-    fn possible_wk(&self) -> Vec<Move> {
-
-        let mut moves: Vec<Move> = Vec::new();
-
-        for i in 0..64 {
-            if self.white_king & 1u64 << i != 0 {
-                let mut possibility: u64;
+    fn possible<const WHITE: bool>(&self, moves: &mut MoveList, captures: bool, quiets: bool) {
+        let mut targets = 0;
 
-                if i > 9 {
-                    possibility = KING_SPAN << (i - 9);
-                } else {
-                    possibility = KING_SPAN >> (9 - i);
-                }
-
-                if i % 8 < 4 {
-                    possibility &= !(FILE_G | FILE_H) & !self.white_pieces;
-                } else {
-                    possibility &= !(FILE_A | FILE_B) & !self.white_pieces;
-                }
+        if captures {
+            targets |= self.side(!WHITE).all();
+        }
+        if quiets {
+            targets |= self.empty_squares;
+        }
 
-                for j in 0..64 {
-                    if possibility & 1u64 << j != 0 {
-                        moves.push(Move::Normal { from: i, to: j });
-                    }
-                }
+        self.possible_pawns::<WHITE>(moves, captures, quiets);
+        self.possible_king::<WHITE>(moves, targets);
+        self.possible_queens::<WHITE>(moves, targets);
+        self.possible_rooks::<WHITE>(moves, targets);
+        self.possible_bishops::<WHITE>(moves, targets);
+        self.possible_knights::<WHITE>(moves, targets);
 
-                break;
-            }
+        if quiets {
+            self.possible_castles::<WHITE>(moves);
         }
-
-        moves
     }
 
     ///
-    /// Get all pseudo-legal pawn moves black can make.
+    /// Adds a normal move from a position to each of the target positions.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// A vector of all pseudo-legal pawn moves black can make.
+    /// * `moves` - The list to add the moves to.
+    /// * `from` - The position of the moved piece as a number between 0 and 63 (both included).
+    /// * `targets` - A bitboard representing the positions moved to.
     ///
-    fn possible_bp(&self) -> Vec<Move> {
-
-        let mut moves: Vec<Move> = Vec::new();
+    fn push_moves(moves: &mut MoveList, from: u8, targets: u64) {
+        let from = Square::new(from);
 
-        // Pawn SW captures
-
-        let mut pawn_moves =
-            (self.black_pawns >> 9) & !FILE_H & self.white_pieces & !RANK_8 & !RANK_1;
-
-        for i in 0..64 {
-            if pawn_moves & (1u64 << i) != 0 {
-                moves.push(Move::Normal { from: i + 9, to: i });
-            }
+        for to in SetBits(targets) {
+            moves.push(Move::Normal {
+                from,
+                to: Square::new(to),
+            });
         }
+    }
 
-        // Pawn SE captures
-
-        pawn_moves = (self.black_pawns >> 7) & !FILE_A & self.white_pieces & !RANK_8 & !RANK_1;
-
-        for i in 0..64 {
-            if pawn_moves & (1u64 << i) != 0 {
-                moves.push(Move::Normal { from: i + 7, to: i });
-            }
-        }
+    ///
+    /// Get the pseudo-legal pawn moves a side can make.
+    ///
+    /// # Arguments
+    ///
+    /// * `WHITE` - Whether the side is white.
+    /// * `moves` - The list to add the pseudo-legal pawn moves of the side to.
+    /// * `captures` - Whether to add the captures and the promotions to a queen.
+    /// * `quiets` - Whether to add the other moves.
+    ///
+    fn possible_pawns<const WHITE: bool>(
+        &self,
+        moves: &mut MoveList,
+        captures: bool,
+        quiets: bool,
+    ) {
+        let own = self.side(WHITE);
+        let enemy = self.side(!WHITE).pieces;
+
+        let (push, double_push_rank, last_rank, promotions) = if WHITE {
+            (
+                8,
+                RANK_3,
+                RANK_8,
+                [
+                    Piece::WhiteBishop,
+                    Piece::WhiteKnight,
+                    Piece::WhiteRook,
+                    Piece::WhiteQueen,
+                ],
+            )
+        } else {
+            (
+                -8,
+                RANK_6,
+                RANK_1,
+                [
+                    Piece::BlackBishop,
+                    Piece::BlackKnight,
+                    Piece::BlackRook,
+                    Piece::BlackQueen,
+                ],
+            )
+        };
 
-        // Pawn forward one
+        // whether to add a move, a capture or a promotion to a queen being added with the captures
+        let wanted = |tactical: bool| if tactical { captures } else { quiets };
 
-        pawn_moves = (self.black_pawns >> 8) & self.empty_squares & !RANK_8 & !RANK_1;
+        // adds the moves to the targets of pawns shifted by the offset, promoting on the last rank
+        let mut push_pawn_moves = |targets: u64, offset: i8, capture: bool| {
+            for to in SetBits(targets) {
+                let promoting = last_rank & (1u64 << to) != 0;
+                let (from, to) = (Square::new((to as i8 - offset) as u8), Square::new(to));
 
-        for i in 0..64 {
-            if pawn_moves & (1u64 << i) != 0 {
-                moves.push(Move::Normal { from: i + 8, to: i });
+                if !promoting {
+                    if wanted(capture) {
+                        moves.push(Move::Normal { from, to });
+                    }
+                } else {
+                    for promotion in promotions {
+                        let queen = matches!(promotion, Piece::WhiteQueen | Piece::BlackQueen);
+
+                        if wanted(capture || queen) {
+                            moves.push(Move::Promotion {
+                                from,
+                                to,
+                                promotion,
+                            });
+                        }
+                    }
+                }
             }
-        }
-
-        // Pawn forward two
+        };
 
-        pawn_moves = (pawn_moves >> 8)
-            & ((self.black_pawns & RANK_7) >> 16 & self.empty_squares & !RANK_8 & !RANK_7);
+        // Pawn captures towards the h-file and towards the a-file
 
-        for i in 0..64 {
-            if pawn_moves & (1u64 << i) != 0 {
-                moves.push(Move::Normal {
-                    from: i + 16,
-                    to: i,
-                });
-            }
+        if captures {
+            push_pawn_moves(shift(own.pawns, push + 1) & !FILE_A & enemy, push + 1, true);
+            push_pawn_moves(shift(own.pawns, push - 1) & !FILE_H & enemy, push - 1, true);
         }
 
-        // Pawn Promotion
-
-        pawn_moves = (self.black_pawns >> 8) & self.empty_squares & RANK_1;
-
-        for i in 0..64 {
-            if pawn_moves & (1u64 << i) != 0 {
-                for promotion in [Piece::BlackBishop, Piece::BlackKnight, Piece::BlackRook, Piece::BlackQueen].iter() {
-                    moves.push(Move::Promotion {
-                        from: i + 8,
-                        to: i,
-                        promotion: *promotion,
-                    });
-                }
-            }
-        }
+        // Pawn forward one and two
 
-        // Pawn Promotion SW captures
+        let single_pushes = shift(own.pawns, push) & self.empty_squares;
 
-        pawn_moves = (self.black_pawns >> 9) & !FILE_H & self.white_pieces & RANK_1;
+        push_pawn_moves(single_pushes, push, false);
 
-        for i in 0..64 {
-            if pawn_moves & (1u64 << i) != 0 {
-                for promotion in [Piece::BlackBishop, Piece::BlackKnight, Piece::BlackRook, Piece::BlackQueen].iter() {
-                    moves.push(Move::Promotion {
-                        from: i + 9,
-                        to: i,
-                        promotion: *promotion,
-                    });
-                }
-            }
+        if quiets {
+            push_pawn_moves(
+                shift(single_pushes & double_push_rank, push) & self.empty_squares,
+                2 * push,
+                false,
+            );
         }
 
-        // Pawn Promotion SE captures
-
-        pawn_moves = (self.black_pawns >> 7) & !FILE_A & self.white_pieces & RANK_1;
+        // Pawn en passant
 
-        for i in 0..64 {
-            if pawn_moves & (1u64 << i) != 0 {
-                for promotion in [Piece::BlackBishop, Piece::BlackKnight, Piece::BlackRook, Piece::BlackQueen].iter() {
-                    moves.push(Move::Promotion {
-                        from: i + 7,
-                        to: i,
-                        promotion: *promotion,
-                    });
-                }
-            }
+        if !captures {
+            return;
         }
 
-        match self.en_passant {
-            Some(en_passant) => {
-                // Pawn SW en passant
-
-                pawn_moves = (self.black_pawns >> 9) & !FILE_H & !RANK_8 & (1u64 << en_passant);
-
-                if pawn_moves != 0 && !self.white_turn {
-                    moves.push(Move::EnPassant {
-                        from: en_passant + 9,
-                        to: en_passant,
-                        captured: en_passant + 8,
-                    });
-                }
-
-                // Pawn SE en passant
-
-                pawn_moves = (self.black_pawns >> 7) & !FILE_A & !RANK_8 & (1u64 << en_passant);
+        if let Some(en_passant) = self.en_passant {
+            if self.white_turn == WHITE {
+                // the pawns attacking the en passant square are those attacked by an enemy pawn
+                // on it
+                let attackers = own.pawns & PAWN_ATTACKS[WHITE as usize][en_passant as usize];
 
-                if pawn_moves != 0 && !self.white_turn {
+                for from in SetBits(attackers) {
                     moves.push(Move::EnPassant {
-                        from: en_passant + 7,
-                        to: en_passant,
-                        captured: en_passant + 8,
+                        from: Square::new(from),
+                        to: Square::new(en_passant),
+                        captured: Square::new((en_passant as i8 - push) as u8),
                     });
                 }
             }
-            None => {}
         }
-
-        moves
     }
 
     ///
-    /// Get all pseudo-legal knight moves black can make.
+    /// Get the pseudo-legal knight moves a side can make to some squares.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// A vector of all pseudo-legal knight moves black can make.
+    /// * `WHITE` - Whether the side is white.
+    /// * `moves` - The list to add the pseudo-legal knight moves of the side to.
+    /// * `targets` - A bitboard representing the squares the moves can go to.
     ///
-    fn possible_bn(&self) -> Vec<Move> {
-
-        let mut moves: Vec<Move> = Vec::new();
-
-        for i in 0..64 {
-            if self.black_knights & 1u64 << i != 0 {
-                let mut possibility: u64;
-
-                if i > 18 {
-                    possibility = KNIGHT_SPAN << (i - 18);
-                } else {
-                    possibility = KNIGHT_SPAN >> (18 - i);
-                }
-
-                if i % 8 < 4 {
-                    possibility &= !(FILE_G | FILE_H) & !self.black_pieces & !self.black_king;
-                } else {
-                    possibility &= !(FILE_A | FILE_B) & !self.black_pieces & !self.black_king;
-                }
+    fn possible_knights<const WHITE: bool>(&self, moves: &mut MoveList, targets: u64) {
+        let own = self.side(WHITE);
 
-                for j in 0..64 {
-                    if possibility & 1u64 << j != 0 {
-                        moves.push(Move::Normal { from: i, to: j });
-                    }
-                }
-            }
+        for from in SetBits(own.knights) {
+            Board::push_moves(
+                moves,
+                from,
+                Board::knight_attacks(from) & targets & !own.all(),
+            );
         }
-
-        moves
     }
 
     ///
-    /// Get all pseudo-legal bishop moves black can make.
+    /// Get the pseudo-legal bishop moves a side can make to some squares.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// A vector of all pseudo-legal bishop moves black can make.
+    /// * `WHITE` - Whether the side is white.
+    /// * `moves` - The list to add the pseudo-legal bishop moves of the side to.
+    /// * `targets` - A bitboard representing the squares the moves can go to.
     ///
-    fn possible_bb(&self) -> Vec<Move> {
-        let mut moves: Vec<Move> = Vec::new();
+    fn possible_bishops<const WHITE: bool>(&self, moves: &mut MoveList, targets: u64) {
+        let own = self.side(WHITE);
 
-        for i in 0..64 {
-            if self.black_bishops & (1u64 << i) != 0 {
-                let bishop_moves = self.possible_da(i) & !self.black_pieces & !self.black_king;
-
-                for j in 0..64 {
-                    if bishop_moves & (1u64 << j) != 0 {
-                        moves.push(Move::Normal { from: i, to: j });
-                    }
-                }
-            }
+        for from in SetBits(own.bishops) {
+            Board::push_moves(moves, from, self.possible_da(from) & targets & !own.all());
         }
-
-        moves
     }
 
     ///
-    /// Get all pseudo-legal rook moves black can make.
+    /// Get the pseudo-legal rook moves a side can make to some squares.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// A vector of all pseudo-legal rook moves black can make.
+    /// * `WHITE` - Whether the side is white.
+    /// * `moves` - The list to add the pseudo-legal rook moves of the side to.
+    /// * `targets` - A bitboard representing the squares the moves can go to.
     ///
-    fn possible_br(&self) -> Vec<Move> {
-        let mut moves: Vec<Move> = Vec::new();
+    fn possible_rooks<const WHITE: bool>(&self, moves: &mut MoveList, targets: u64) {
+        let own = self.side(WHITE);
 
-        for i in 0..64 {
-            if self.black_rooks & (1u64 << i) != 0 {
-                let rook_moves = self.possible_hv(i) & !self.black_pieces & !self.black_king;
-
-                for j in 0..64 {
-                    if rook_moves & (1u64 << j) != 0 {
-                        moves.push(Move::Normal { from: i, to: j });
-                    }
-                }
-            }
+        for from in SetBits(own.rooks) {
+            Board::push_moves(moves, from, self.possible_hv(from) & targets & !own.all());
         }
-
-        moves
     }
 
     ///
-    /// Get all pseudo-legal queen moves black can make.
+    /// Get the pseudo-legal queen moves a side can make to some squares.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// A vector of all pseudo-legal queen moves black can make.
+    /// * `WHITE` - Whether the side is white.
+    /// * `moves` - The list to add the pseudo-legal queen moves of the side to.
+    /// * `targets` - A bitboard representing the squares the moves can go to.
     ///
-    fn possible_bq(&self) -> Vec<Move> {
-        let mut moves: Vec<Move> = Vec::new();
+    fn possible_queens<const WHITE: bool>(&self, moves: &mut MoveList, targets: u64) {
+        let own = self.side(WHITE);
 
-        for i in 0..64 {
-            if self.black_queens & (1u64 << i) != 0 {
-                let queen_moves = (self.possible_hv(i) | self.possible_da(i))
-                    & !self.black_pieces
-                    & !self.black_king;
-
-                for j in 0..64 {
-                    if queen_moves & (1u64 << j) != 0 {
-                        moves.push(Move::Normal { from: i, to: j });
-                    }
-                }
-            }
+        for from in SetBits(own.queens) {
+            let attacks = self.possible_hv(from) | self.possible_da(from);
+            Board::push_moves(moves, from, attacks & targets & !own.all());
         }
-
-        moves
     }
 
     ///
-    /// Get all pseudo-legal king moves black can make.
+    /// Get the pseudo-legal king moves a side can make to some squares.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// A vector of all pseudo-legal king moves black can make.
+    /// * `WHITE` - Whether the side is white.
+    /// * `moves` - The list to add the pseudo-legal king moves of the side to.
+    /// * `targets` - A bitboard representing the squares the moves can go to.
     ///
-    fn possible_bk(&self) -> Vec<Move> {
+    fn possible_king<const WHITE: bool>(&self, moves: &mut MoveList, targets: u64) {
+        let own = self.side(WHITE);
 
-        let mut moves: Vec<Move> = Vec::new();
-
-        for i in 0..64 {
-            if self.black_king & 1u64 << i != 0 {
-                let mut possibility: u64;
-
-                if i > 9 {
-                    possibility = KING_SPAN << (i - 9);
-                } else {
-                    possibility = KING_SPAN >> (9 - i);
-                }
-
-                if i % 8 < 4 {
-                    possibility &= !(FILE_G | FILE_H) & !self.black_pieces;
-                } else {
-                    possibility &= !(FILE_A | FILE_B) & !self.black_pieces;
-                }
-
-                for j in 0..64 {
-                    if possibility & 1u64 << j != 0 {
-                        moves.push(Move::Normal { from: i, to: j });
-                    }
-                }
-
-                break;
-            }
+        for from in SetBits(own.king) {
+            Board::push_moves(
+                moves,
+                from,
+                Board::king_attacks(from) & targets & !own.all(),
+            );
         }
-
-        moves
     }
 
     ///
-    /// Get all castle moves white can make.
+    /// Get all castle moves a side can make.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// A vector of all castle moves white can make.
+    /// * `WHITE` - Whether the side is white.
+    /// * `moves` - The list to add all castle moves of the side to.
     ///
-    fn possible_wc(&self) -> Vec<Move> {
-
-        let mut moves: Vec<Move> = Vec::new();
-
-        // King side castle
-        if self.white_castle_kingside {
-            let unsafe_w = self.unsafe_w();
-
-            // Positions
-            // 4 : King's position. Should not be in check.
-            // 5 : Position between king and rook. Should be empty and safe.
-            // 6 : Position between king and rook. Should be empty and safe.
-            // 7 : Rook's position. Should contain a rook.
-            if unsafe_w & (1u64 << 4) == 0
-                && (unsafe_w | !self.empty_squares) & 1u64 << 5 == 0
-                && (unsafe_w | !self.empty_squares) & 1u64 << 6 == 0
-                && self.white_rooks & 1u64 << 7 != 0
-            {
-                moves.push(Move::Castle {
-                    from: 4,
-                    to: 6,
-                    rook: 7,
-                });
-            }
-        }
+    fn possible_castles<const WHITE: bool>(&self, moves: &mut MoveList) {
+        let (kingside, queenside, home) = if WHITE {
+            (self.white_castle_kingside, self.white_castle_queenside, 0)
+        } else {
+            (self.black_castle_kingside, self.black_castle_queenside, 56)
+        };
 
-        // Queen side castle
-        if self.white_castle_queenside {
-            let unsafe_w = self.unsafe_w();
-
-            // Positions
-            // 4 : King's position. Should not be in check.
-            // 3 : Position between king and rook. Should be empty and safe.
-            // 2 : Position between king and rook. Should be empty and safe.
-            // 1 : Position between king and rook. Should be empty.
-            // 0 : Rook's position. Should contain a rook.
-            if !self.empty_squares & (1u64 << 1) == 0
-                && (unsafe_w | !self.empty_squares) & (1u64 << 2) == 0
-                && (unsafe_w | !self.empty_squares) & (1u64 << 3) == 0
-                && unsafe_w & (1u64 << 4) == 0
-            {
-                moves.push(Move::Castle {
-                    from: 4,
-                    to: 2,
-                    rook: 0,
-                });
-            }
+        if !kingside && !queenside {
+            return;
         }
 
-        moves
-    }
-
-    ///
-    /// Get all castle moves black can make.
-    ///     
-    /// # Returns
-    ///
-    /// A vector of all castle moves black can make.
-    ///
-    fn possible_bc(&self) -> Vec<Move> {
+        let own = self.side(WHITE);
+        let unsafe_squares = self.unsafe_squares::<WHITE>();
+        let occupied = !self.empty_squares;
 
-        let mut moves: Vec<Move> = Vec::new();
+        // Positions, relative to the first square of the home rank
+        // 4 : King's position. Should contain the king and not be in check.
+        if own.king & (1u64 << (home + 4)) == 0 || unsafe_squares & (1u64 << (home + 4)) != 0 {
+            return;
+        }
 
         // King side castle
-        if self.black_castle_kingside {
-            let unsafe_b = self.unsafe_b();
-
-            // Positions
-            // 60 : King's position. Should not be in check.
-            // 61 : Position between king and rook. Should be empty and safe.
-            // 62 : Position between king and rook. Should be empty and safe.
-            // 63 : Rook's position. Should contain a rook.
-            if unsafe_b & (1u64 << 60) == 0
-                && (unsafe_b | !self.empty_squares) & (1u64 << 61) == 0
-                && (unsafe_b | !self.empty_squares) & (1u64 << 62) == 0
-                && self.black_rooks & (1u64 << 63) != 0
-            {
-                moves.push(Move::Castle {
-                    from: 60,
-                    to: 62,
-                    rook: 63,
-                });
-            }
+        // 5 : Position between king and rook. Should be empty and safe.
+        // 6 : Position between king and rook. Should be empty and safe.
+        // 7 : Rook's position. Should contain a rook.
+        if kingside
+            && (unsafe_squares | occupied) & (0b0110_0000u64 << home) == 0
+            && own.rooks & (1u64 << (home + 7)) != 0
+        {
+            moves.push(Move::Castle {
+                from: Square::new(home + 4),
+                to: Square::new(home + 6),
+                rook: Square::new(home + 7),
+            });
         }
 
         // Queen side castle
-        if self.black_castle_queenside {
-            let unsafe_b = self.unsafe_b();
-
-            // Positions
-            // 60 : King's position. Should not be in check.
-            // 59 : Position between king and rook. Should be empty and safe.
-            // 58 : Position between king and rook. Should be empty and safe.
-            // 57 : Position between king and rook. Should be empty.
-            // 56 : Rook's position. Should contain a rook.
-            if self.black_rooks & (1u64 << 56) != 0
-                && !self.empty_squares & (1u64 << 57) == 0
-                && (unsafe_b | !self.empty_squares) & (1u64 << 58) == 0
-                && (unsafe_b | !self.empty_squares) & (1u64 << 59) == 0
-                && unsafe_b & (1u64 << 60) == 0
-            {
-                moves.push(Move::Castle {
-                    from: 60,
-                    to: 58,
-                    rook: 56,
-                });
-            }
+        // 3 : Position between king and rook. Should be empty and safe.
+        // 2 : Position between king and rook. Should be empty and safe.
+        // 1 : Position between king and rook. Should be empty.
+        // 0 : Rook's position. Should contain a rook.
+        if queenside
+            && (unsafe_squares | occupied) & (0b0000_1100u64 << home) == 0
+            && occupied & (1u64 << (home + 1)) == 0
+            && own.rooks & (1u64 << home) != 0
+        {
+            moves.push(Move::Castle {
+                from: Square::new(home + 4),
+                to: Square::new(home + 2),
+                rook: Square::new(home),
+            });
         }
-
-        moves
     }
 
     ///
-    /// Get all unsafe squares for white.
+    /// Get the pseudo-legal quiet moves of a side that check the king of the other side.
     ///
-    /// # Returns
+    /// A piece checks by moving to a square from which it attacks the king, or discovers a check
+    /// by moving off the line between the king and a slider of the side. The attacks of promoted
+    /// pieces and castled rooks are tested one by one, as the moved piece changes.
     ///
-    /// A bitboard representing all squares attacked by black.
+    /// # Arguments
     ///
-    fn unsafe_w(&self) -> u64 {
-        let mut unsafe_squares: u64 = 0;
-
-        // pawn
-        unsafe_squares |= (self.black_pawns >> 7) & !FILE_A;
-        unsafe_squares |= (self.black_pawns >> 9) & !FILE_H;
-
-        // knight
-
-        for i in 0..64 {
-            if self.black_knights & 1u64 << i != 0 {
-                let mut possibility: u64;
-
-                if i > 18 {
-                    possibility = KNIGHT_SPAN << (i - 18);
-                } else {
-                    possibility = KNIGHT_SPAN >> (18 - i);
-                }
-
-                if i % 8 < 4 {
-                    possibility &= !(FILE_G | FILE_H) & !self.black_pieces & !self.black_king;
-                } else {
-                    possibility &= !(FILE_A | FILE_B) & !self.black_pieces & !self.black_king;
-                };
+    /// * `WHITE` - Whether the side is white.
+    /// * `moves` - The list to add the quiet checks of the side to.
+    ///
+    fn quiet_checks<const WHITE: bool>(&self, moves: &mut MoveList) {
+        let own = self.side(WHITE);
+        let enemy_king = self.side(!WHITE).king;
 
-                unsafe_squares |= possibility;
-            }
+        if enemy_king.count_ones() != 1 {
+            return;
         }
 
-        // bishop | queen
-
-        let bishop_queen = self.black_bishops | self.black_queens;
+        let king_position = enemy_king.trailing_zeros() as u8;
+        let occupied = !self.empty_squares;
 
-        for i in 0..64 {
-            if bishop_queen & 1u64 << i != 0 {
-                unsafe_squares |= self.possible_da(i);
+        // squares from which each kind of piece attacks the king
+        let pawn_checks = PAWN_ATTACKS[WHITE as usize][king_position as usize];
+        let knight_checks = Board::knight_attacks(king_position);
+        let bishop_checks = Board::da_attacks(king_position, occupied);
+        let rook_checks = Board::hv_attacks(king_position, occupied);
+
+        // pieces standing alone between the king and a slider of the side, with the line they
+        // have to leave to discover a check
+        let mut lines = [0u64; 64];
+        let mut candidates = 0;
+        let sliders = (Board::hv_attacks(king_position, occupied & !own.all())
+            & (own.rooks | own.queens))
+            | (Board::da_attacks(king_position, occupied & !own.all())
+                & (own.bishops | own.queens));
+
+        for i in SetBits(sliders) {
+            let between = Board::squares_between(king_position, i);
+            let blockers = between & occupied;
+
+            if blockers.count_ones() == 1 && blockers & own.all() != 0 {
+                candidates |= blockers;
+                lines[blockers.trailing_zeros() as usize] = between | (1u64 << i);
             }
         }
 
-        // rook | queen
+        // empty squares a piece checks from, directly or by discovering a check
+        let targets = |from: u8, direct: u64| {
+            let discovered = if candidates & (1u64 << from) != 0 {
+                !lines[from as usize]
+            } else {
+                0
+            };
 
-        let rook_queen = self.black_rooks | self.black_queens;
+            self.empty_squares & (direct | discovered)
+        };
 
-        for i in 0..64 {
-            if rook_queen & 1u64 << i != 0 {
-                unsafe_squares |= self.possible_hv(i);
-            }
+        for from in SetBits(own.knights) {
+            let checks = targets(from, knight_checks);
+            Board::push_moves(moves, from, Board::knight_attacks(from) & checks);
         }
-
-        // king
-
-        for i in 0..64 {
-            if self.black_king & 1u64 << i != 0 {
-                let mut possibility: u64;
-
-                if i > 9 {
-                    possibility = KING_SPAN << (i - 9);
-                } else {
-                    possibility = KING_SPAN >> (9 - i);
-                }
-
-                if i % 8 < 4 {
-                    possibility &= !(FILE_G | FILE_H);
-                } else {
-                    possibility &= !(FILE_A | FILE_B);
-                }
-
-                unsafe_squares |= possibility;
-            }
+        for from in SetBits(own.bishops) {
+            Board::push_moves(
+                moves,
+                from,
+                self.possible_da(from) & targets(from, bishop_checks),
+            );
         }
-
-        unsafe_squares
-    }
-
-    ///
-    /// Get all unsafe squares for black.
-    ///
-    /// # Returns
-    ///
-    /// A bitboard representing all squares attacked by white.
-    ///
-    fn unsafe_b(&self) -> u64 {
-        let mut unsafe_squares: u64 = 0;
-
-        // pawn
-        unsafe_squares |= (self.white_pawns << 7) & !FILE_H;
-        unsafe_squares |= (self.white_pawns << 9) & !FILE_A;
-
-        // knight
-
-        for i in 0..64 {
-            if self.white_knights & 1u64 << i != 0 {
-                let mut possibility: u64;
-
-                if i > 18 {
-                    possibility = KNIGHT_SPAN << (i - 18);
-                } else {
-                    possibility = KNIGHT_SPAN >> (18 - i);
-                }
-
-                if i % 8 < 4 {
-                    possibility &= !(FILE_G | FILE_H) & !self.white_pieces & !self.white_king;
-                } else {
-                    possibility &= !(FILE_A | FILE_B) & !self.white_pieces & !self.white_king;
-                };
-
-                unsafe_squares |= possibility;
-            }
+        for from in SetBits(own.rooks) {
+            Board::push_moves(
+                moves,
+                from,
+                self.possible_hv(from) & targets(from, rook_checks),
+            );
         }
-
-        // bishop | queen
-
-        let bishop_queen = self.white_bishops | self.white_queens;
-
-        for i in 0..64 {
-            if bishop_queen & 1u64 << i != 0 {
-                unsafe_squares |= self.possible_da(i);
-            }
+        for from in SetBits(own.queens) {
+            let attacks = self.possible_hv(from) | self.possible_da(from);
+            Board::push_moves(
+                moves,
+                from,
+                attacks & targets(from, bishop_checks | rook_checks),
+            );
+        }
+        for from in SetBits(own.king) {
+            Board::push_moves(moves, from, Board::king_attacks(from) & targets(from, 0));
         }
 
-        // rook | queen
+        let (push, double_push_rank, last_rank, promotions) = if WHITE {
+            (
+                8,
+                RANK_3,
+                RANK_8,
+                [Piece::WhiteKnight, Piece::WhiteBishop, Piece::WhiteRook],
+            )
+        } else {
+            (
+                -8,
+                RANK_6,
+                RANK_1,
+                [Piece::BlackKnight, Piece::BlackBishop, Piece::BlackRook],
+            )
+        };
 
-        let rook_queen = self.white_rooks | self.white_queens;
+        for from in SetBits(own.pawns) {
+            let single_push = shift(1u64 << from, push) & self.empty_squares;
+            let double_push = shift(single_push & double_push_rank, push) & self.empty_squares;
+            let checks = targets(from, pawn_checks);
 
-        for i in 0..64 {
-            if rook_queen & 1u64 << i != 0 {
-                unsafe_squares |= self.possible_hv(i);
+            Board::push_moves(
+                moves,
+                from,
+                (single_push | double_push) & !last_rank & checks,
+            );
+
+            // an underpromoted piece sees through the square the pawn left
+            for to in SetBits(single_push & last_rank) {
+                let occupied = occupied & !(1u64 << from);
+
+                for promotion in promotions {
+                    let attacks = match promotion {
+                        Piece::WhiteKnight | Piece::BlackKnight => Board::knight_attacks(to),
+                        Piece::WhiteBishop | Piece::BlackBishop => Board::da_attacks(to, occupied),
+                        _ => Board::hv_attacks(to, occupied),
+                    };
+
+                    if (attacks & enemy_king) | (targets(from, 0) & (1u64 << to)) != 0 {
+                        moves.push(Move::Promotion {
+                            from: Square::new(from),
+                            to: Square::new(to),
+                            promotion,
+                        });
+                    }
+                }
             }
         }
 
-        // king
-
-        for i in 0..64 {
-            if self.white_king & 1u64 << i != 0 {
-                let mut possibility: u64;
+        // the king on its home rank can not stand between a slider and the other king, so only
+        // the castled rook can check
+        let mut castles = MoveList::new();
+        self.possible_castles::<WHITE>(&mut castles);
 
-                if i > 9 {
-                    possibility = KING_SPAN << (i - 9);
-                } else {
-                    possibility = KING_SPAN >> (9 - i);
-                }
+        for m in castles {
+            if let Move::Castle { from, to, rook } = m {
+                let rook_to = Board::castled_rook(from, to).index();
+                let occupied = (occupied & !(1u64 << from.index() | 1u64 << rook.index()))
+                    | 1u64 << to.index()
+                    | 1u64 << rook_to;
 
-                if i % 8 < 4 {
-                    possibility &= !(FILE_G | FILE_H);
-                } else {
-                    possibility &= !(FILE_A | FILE_B);
+                if Board::hv_attacks(rook_to, occupied) & enemy_king != 0 {
+                    moves.push(m);
                 }
-
-                unsafe_squares |= possibility;
             }
         }
+    }
+
+    ///
+    /// Get all unsafe squares for a side.
+    ///
+    /// # Arguments
+    ///
+    /// * `WHITE` - Whether the side is white.
+    ///
+    /// # Returns
+    ///
+    /// A bitboard representing all squares attacked by the other side.
+    ///
+    fn unsafe_squares<const WHITE: bool>(&self) -> u64 {
+        self.attacked_by(!WHITE, !self.empty_squares)
+    }
+}
+
+impl FromStr for Board {
+    type Err = FenError;
+
+    fn from_str(input: &str) -> Result<Board, FenError> {
+        Board::from_fen(input)
+    }
+}
 
-        unsafe_squares
+impl TryFrom<&str> for Board {
+    type Error = FenError;
+
+    fn try_from(input: &str) -> Result<Board, FenError> {
+        Board::from_fen(input)
     }
 }
 
@@ -1760,7 +3670,10 @@ impl fmt::Display for Board {
 fn make_pawn_move_n() {
     let board = Board::new("8/8/8/8/8/4P3/8/8 w - - 0 1");
 
-    let m = Move::Normal { from: 20, to: 28 };
+    let m = Move::Normal {
+        from: Square::E3,
+        to: Square::E4,
+    };
     let new_white_pawns = board.move_board(&m, Piece::WhitePawn);
 
     assert_eq!(new_white_pawns, 1u64 << 28);
@@ -1770,7 +3683,10 @@ fn make_pawn_move_n() {
 fn make_rook_capture() {
     let board = Board::new("8/4r3/8/8/8/8/4R3/8 w - - 0 1");
 
-    let m = Move::Normal { from: 12, to: 52 };
+    let m = Move::Normal {
+        from: Square::E2,
+        to: Square::E7,
+    };
 
     let new_white_rooks = board.move_board(&m, Piece::WhiteRook);
     let new_black_rooks = board.move_board(&m, Piece::BlackRook);
@@ -1784,8 +3700,8 @@ fn make_pawn_promotion() {
     let board = Board::new("4p3/3P4/8/8/8/8/8/8 w - - 0 1");
 
     let m = Move::Promotion {
-        from: 51,
-        to: 60,
+        from: Square::D7,
+        to: Square::E8,
         promotion: Piece::WhiteQueen,
     };
 
@@ -1803,9 +3719,9 @@ fn make_en_passant() {
     let board = Board::new("8/8/8/2pPp3/8/8/8/8 w - e6 0 1");
 
     let m = Move::EnPassant {
-        from: 35,
-        to: 44,
-        captured: 36,
+        from: Square::D5,
+        to: Square::E6,
+        captured: Square::E5,
     };
 
     let new_white_pawns = board.move_board(&m, Piece::WhitePawn);
@@ -1820,9 +3736,9 @@ fn make_castle_w_k() {
     let board = Board::new("8/8/8/8/8/8/8/4K2R w KQ - 0 1");
 
     let m = Move::Castle {
-        from: 4,
-        to: 6,
-        rook: 7,
+        from: Square::E1,
+        to: Square::G1,
+        rook: Square::H1,
     };
 
     let new_white_kings = board.move_board(&m, Piece::WhiteKing);
@@ -1837,9 +3753,9 @@ fn make_castle_w_q() {
     let board = Board::new("8/8/8/8/8/8/8/R3K3 w KQ - 0 1");
 
     let m = Move::Castle {
-        from: 4,
-        to: 2,
-        rook: 0,
+        from: Square::E1,
+        to: Square::C1,
+        rook: Square::A1,
     };
 
     let new_white_kings = board.move_board(&m, Piece::WhiteKing);
@@ -1852,8 +3768,12 @@ fn make_castle_w_q() {
 #[test]
 fn pawn_capture_nw() {
     let board = Board::new("8/8/8/p5pp/P6P/8/8/8 w - - 0 1");
-    let moves = board.possible_wp();
-    let correct_moves: Vec<Move> = vec![Move::Normal { from: 31, to: 38 }];
+    let mut moves = MoveList::new();
+    board.possible_pawns::<true>(&mut moves, true, true);
+    let correct_moves: Vec<Move> = vec![Move::Normal {
+        from: Square::H4,
+        to: Square::G5,
+    }];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
         assert!(correct_moves.contains(&m));
@@ -1863,8 +3783,12 @@ fn pawn_capture_nw() {
 #[test]
 fn pawn_capture_ne() {
     let board = Board::new("8/8/8/pp5p/P6P/8/8/8 w - - 0 1");
-    let moves = board.possible_wp();
-    let correct_moves: Vec<Move> = vec![Move::Normal { from: 24, to: 33 }];
+    let mut moves = MoveList::new();
+    board.possible_pawns::<true>(&mut moves, true, true);
+    let correct_moves: Vec<Move> = vec![Move::Normal {
+        from: Square::A4,
+        to: Square::B5,
+    }];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
         assert!(correct_moves.contains(&m));
@@ -1874,8 +3798,12 @@ fn pawn_capture_ne() {
 #[test]
 fn pawn_move_n() {
     let board = Board::new("8/8/2p5/4p3/2P1P3/8/8/8 w - - 0 1");
-    let moves = board.possible_wp();
-    let correct_moves: Vec<Move> = vec![Move::Normal { from: 26, to: 34 }];
+    let mut moves = MoveList::new();
+    board.possible_pawns::<true>(&mut moves, true, true);
+    let correct_moves: Vec<Move> = vec![Move::Normal {
+        from: Square::C4,
+        to: Square::C5,
+    }];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
         assert!(correct_moves.contains(&m));
@@ -1885,13 +3813,29 @@ fn pawn_move_n() {
 #[test]
 fn pawn_move_nn() {
     let board = Board::new("8/8/6p1/2p1p3/p7/4P1P1/P1P5/8 w - - 0 1");
-    let moves = board.possible_wp();
+    let mut moves = MoveList::new();
+    board.possible_pawns::<true>(&mut moves, true, true);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 8, to: 16 },
-        Move::Normal { from: 10, to: 18 },
-        Move::Normal { from: 10, to: 26 },
-        Move::Normal { from: 20, to: 28 },
-        Move::Normal { from: 22, to: 30 },
+        Move::Normal {
+            from: Square::A2,
+            to: Square::A3,
+        },
+        Move::Normal {
+            from: Square::C2,
+            to: Square::C3,
+        },
+        Move::Normal {
+            from: Square::C2,
+            to: Square::C4,
+        },
+        Move::Normal {
+            from: Square::E3,
+            to: Square::E4,
+        },
+        Move::Normal {
+            from: Square::G3,
+            to: Square::G4,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -1902,26 +3846,27 @@ fn pawn_move_nn() {
 #[test]
 fn pawn_promotion_n() {
     let board = Board::new("8/3P4/8/8/8/8/8/8 w - - 0 1");
-    let moves = board.possible_wp();
+    let mut moves = MoveList::new();
+    board.possible_pawns::<true>(&mut moves, true, true);
     let correct_moves: Vec<Move> = vec![
         Move::Promotion {
-            from: 51,
-            to: 59,
+            from: Square::D7,
+            to: Square::D8,
             promotion: Piece::WhiteRook,
         },
         Move::Promotion {
-            from: 51,
-            to: 59,
+            from: Square::D7,
+            to: Square::D8,
             promotion: Piece::WhiteBishop,
         },
         Move::Promotion {
-            from: 51,
-            to: 59,
+            from: Square::D7,
+            to: Square::D8,
             promotion: Piece::WhiteKnight,
         },
         Move::Promotion {
-            from: 51,
-            to: 59,
+            from: Square::D7,
+            to: Square::D8,
             promotion: Piece::WhiteQueen,
         },
     ];
@@ -1934,26 +3879,27 @@ fn pawn_promotion_n() {
 #[test]
 fn pawn_promotion_captures_n() {
     let board = Board::new("3pp3/3P4/8/8/8/8/8/8 w - - 0 1");
-    let moves = board.possible_wp();
+    let mut moves = MoveList::new();
+    board.possible_pawns::<true>(&mut moves, true, true);
     let correct_moves: Vec<Move> = vec![
         Move::Promotion {
-            from: 51,
-            to: 60,
+            from: Square::D7,
+            to: Square::E8,
             promotion: Piece::WhiteRook,
         },
         Move::Promotion {
-            from: 51,
-            to: 60,
+            from: Square::D7,
+            to: Square::E8,
             promotion: Piece::WhiteBishop,
         },
         Move::Promotion {
-            from: 51,
-            to: 60,
+            from: Square::D7,
+            to: Square::E8,
             promotion: Piece::WhiteKnight,
         },
         Move::Promotion {
-            from: 51,
-            to: 60,
+            from: Square::D7,
+            to: Square::E8,
             promotion: Piece::WhiteQueen,
         },
     ];
@@ -1967,13 +3913,17 @@ fn pawn_promotion_captures_n() {
 #[test]
 fn en_passant_ne() {
     let board = Board::new("8/8/8/2pPp3/8/8/8/8 w - e6 0 1");
-    let moves = board.possible_wp();
+    let mut moves = MoveList::new();
+    board.possible_pawns::<true>(&mut moves, true, true);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 35, to: 43 },
+        Move::Normal {
+            from: Square::D5,
+            to: Square::D6,
+        },
         Move::EnPassant {
-            from: 35,
-            to: 44,
-            captured: 36,
+            from: Square::D5,
+            to: Square::E6,
+            captured: Square::E5,
         },
     ];
     assert_eq!(moves.len(), correct_moves.len());
@@ -1985,13 +3935,17 @@ fn en_passant_ne() {
 #[test]
 fn en_passant_nw() {
     let board = Board::new("8/8/8/2pPp3/8/8/8/8 w - c6 0 1");
-    let moves = board.possible_wp();
+    let mut moves = MoveList::new();
+    board.possible_pawns::<true>(&mut moves, true, true);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 35, to: 43 },
+        Move::Normal {
+            from: Square::D5,
+            to: Square::D6,
+        },
         Move::EnPassant {
-            from: 35,
-            to: 42,
-            captured: 34,
+            from: Square::D5,
+            to: Square::C6,
+            captured: Square::C5,
         },
     ];
     assert_eq!(moves.len(), correct_moves.len());
@@ -2003,8 +3957,12 @@ fn en_passant_nw() {
 #[test]
 fn en_passant_border_ne() {
     let board = Board::new("8/8/8/p6P/8/8/8/8 w - a6 0 1");
-    let moves = board.possible_wp();
-    let correct_moves: Vec<Move> = vec![Move::Normal { from: 39, to: 47 }];
+    let mut moves = MoveList::new();
+    board.possible_pawns::<true>(&mut moves, true, true);
+    let correct_moves: Vec<Move> = vec![Move::Normal {
+        from: Square::H5,
+        to: Square::H6,
+    }];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
         assert!(correct_moves.contains(&m));
@@ -2014,8 +3972,12 @@ fn en_passant_border_ne() {
 #[test]
 fn en_passant_border_nw() {
     let board = Board::new("8/8/8/P6p/8/8/8/8 w - h6 0 1");
-    let moves = board.possible_wp();
-    let correct_moves: Vec<Move> = vec![Move::Normal { from: 32, to: 40 }];
+    let mut moves = MoveList::new();
+    board.possible_pawns::<true>(&mut moves, true, true);
+    let correct_moves: Vec<Move> = vec![Move::Normal {
+        from: Square::A5,
+        to: Square::A6,
+    }];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
         assert!(correct_moves.contains(&m));
@@ -2025,8 +3987,12 @@ fn en_passant_border_nw() {
 #[test]
 fn pawn_capture_sw() {
     let board = Board::new("8/8/8/p6p/P5PP/8/8/8 w - - 0 1");
-    let moves = board.possible_bp();
-    let correct_moves: Vec<Move> = vec![Move::Normal { from: 39, to: 30 }];
+    let mut moves = MoveList::new();
+    board.possible_pawns::<false>(&mut moves, true, true);
+    let correct_moves: Vec<Move> = vec![Move::Normal {
+        from: Square::H5,
+        to: Square::G4,
+    }];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
         assert!(correct_moves.contains(&m));
@@ -2036,8 +4002,12 @@ fn pawn_capture_sw() {
 #[test]
 fn pawn_capture_se() {
     let board = Board::new("8/8/8/p6p/PP5P/8/8/8 w - - 0 1");
-    let moves = board.possible_bp();
-    let correct_moves: Vec<Move> = vec![Move::Normal { from: 32, to: 25 }];
+    let mut moves = MoveList::new();
+    board.possible_pawns::<false>(&mut moves, true, true);
+    let correct_moves: Vec<Move> = vec![Move::Normal {
+        from: Square::A5,
+        to: Square::B4,
+    }];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
         assert!(correct_moves.contains(&m));
@@ -2047,8 +4017,12 @@ fn pawn_capture_se() {
 #[test]
 fn pawn_move_s() {
     let board = Board::new("8/8/8/2p1p3/4P3/2P5/8/8 w - - 0 1");
-    let moves = board.possible_bp();
-    let correct_moves: Vec<Move> = vec![Move::Normal { from: 34, to: 26 }];
+    let mut moves = MoveList::new();
+    board.possible_pawns::<false>(&mut moves, true, true);
+    let correct_moves: Vec<Move> = vec![Move::Normal {
+        from: Square::C5,
+        to: Square::C4,
+    }];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
         assert!(correct_moves.contains(&m));
@@ -2058,13 +4032,29 @@ fn pawn_move_s() {
 #[test]
 fn pawn_move_ss() {
     let board = Board::new("8/p1p5/4p1p1/P7/2P1P3/6P1/8/8 w - - 0 1");
-    let moves = board.possible_bp();
+    let mut moves = MoveList::new();
+    board.possible_pawns::<false>(&mut moves, true, true);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 48, to: 40 },
-        Move::Normal { from: 50, to: 42 },
-        Move::Normal { from: 50, to: 34 },
-        Move::Normal { from: 44, to: 36 },
-        Move::Normal { from: 46, to: 38 },
+        Move::Normal {
+            from: Square::A7,
+            to: Square::A6,
+        },
+        Move::Normal {
+            from: Square::C7,
+            to: Square::C6,
+        },
+        Move::Normal {
+            from: Square::C7,
+            to: Square::C5,
+        },
+        Move::Normal {
+            from: Square::E6,
+            to: Square::E5,
+        },
+        Move::Normal {
+            from: Square::G6,
+            to: Square::G5,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2075,26 +4065,27 @@ fn pawn_move_ss() {
 #[test]
 fn pawn_promotion_s() {
     let board = Board::new("8/8/8/8/8/8/3p4/8 w - - 0 1");
-    let moves = board.possible_bp();
+    let mut moves = MoveList::new();
+    board.possible_pawns::<false>(&mut moves, true, true);
     let correct_moves: Vec<Move> = vec![
         Move::Promotion {
-            from: 11,
-            to: 3,
+            from: Square::D2,
+            to: Square::D1,
             promotion: Piece::BlackRook,
         },
         Move::Promotion {
-            from: 11,
-            to: 3,
+            from: Square::D2,
+            to: Square::D1,
             promotion: Piece::BlackBishop,
         },
         Move::Promotion {
-            from: 11,
-            to: 3,
+            from: Square::D2,
+            to: Square::D1,
             promotion: Piece::BlackKnight,
         },
         Move::Promotion {
-            from: 11,
-            to: 3,
+            from: Square::D2,
+            to: Square::D1,
             promotion: Piece::BlackQueen,
         },
     ];
@@ -2107,26 +4098,27 @@ fn pawn_promotion_s() {
 #[test]
 fn pawn_promotion_captures_s() {
     let board = Board::new("8/8/8/8/8/8/3p4/3PP3 w - - 0 1");
-    let moves = board.possible_bp();
+    let mut moves = MoveList::new();
+    board.possible_pawns::<false>(&mut moves, true, true);
     let correct_moves: Vec<Move> = vec![
         Move::Promotion {
-            from: 11,
-            to: 4,
+            from: Square::D2,
+            to: Square::E1,
             promotion: Piece::BlackRook,
         },
         Move::Promotion {
-            from: 11,
-            to: 4,
+            from: Square::D2,
+            to: Square::E1,
             promotion: Piece::BlackBishop,
         },
         Move::Promotion {
-            from: 11,
-            to: 4,
+            from: Square::D2,
+            to: Square::E1,
             promotion: Piece::BlackKnight,
         },
         Move::Promotion {
-            from: 11,
-            to: 4,
+            from: Square::D2,
+            to: Square::E1,
             promotion: Piece::BlackQueen,
         },
     ];
@@ -2139,13 +4131,17 @@ fn pawn_promotion_captures_s() {
 #[test]
 fn en_passant_se() {
     let board = Board::new("8/8/8/8/2PpP3/8/8/8 b - e3 0 1");
-    let moves = board.possible_bp();
+    let mut moves = MoveList::new();
+    board.possible_pawns::<false>(&mut moves, true, true);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 27, to: 19 },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D3,
+        },
         Move::EnPassant {
-            from: 27,
-            to: 20,
-            captured: 28,
+            from: Square::D4,
+            to: Square::E3,
+            captured: Square::E4,
         },
     ];
     assert_eq!(moves.len(), correct_moves.len());
@@ -2157,13 +4153,17 @@ fn en_passant_se() {
 #[test]
 fn en_passant_sw() {
     let board = Board::new("8/8/8/8/2PpP3/8/8/8 b - c3 0 1");
-    let moves = board.possible_bp();
+    let mut moves = MoveList::new();
+    board.possible_pawns::<false>(&mut moves, true, true);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 27, to: 19 },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D3,
+        },
         Move::EnPassant {
-            from: 27,
-            to: 18,
-            captured: 26,
+            from: Square::D4,
+            to: Square::C3,
+            captured: Square::C4,
         },
     ];
     for m in moves {
@@ -2174,8 +4174,12 @@ fn en_passant_sw() {
 #[test]
 fn en_passant_border_se() {
     let board = Board::new("8/8/8/8/P6p/8/8/8 b - h6 0 1");
-    let moves = board.possible_bp();
-    let correct_moves: Vec<Move> = vec![Move::Normal { from: 31, to: 23 }];
+    let mut moves = MoveList::new();
+    board.possible_pawns::<false>(&mut moves, true, true);
+    let correct_moves: Vec<Move> = vec![Move::Normal {
+        from: Square::H4,
+        to: Square::H3,
+    }];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
         assert!(correct_moves.contains(&m));
@@ -2185,8 +4189,12 @@ fn en_passant_border_se() {
 #[test]
 fn en_passant_border_sw() {
     let board = Board::new("8/8/8/8/p6P/8/8/8 b - a6 0 1");
-    let moves = board.possible_bp();
-    let correct_moves: Vec<Move> = vec![Move::Normal { from: 24, to: 16 }];
+    let mut moves = MoveList::new();
+    board.possible_pawns::<false>(&mut moves, true, true);
+    let correct_moves: Vec<Move> = vec![Move::Normal {
+        from: Square::A4,
+        to: Square::A3,
+    }];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
         assert!(correct_moves.contains(&m));
@@ -2196,22 +4204,65 @@ fn en_passant_border_sw() {
 #[test]
 fn w_rook_move_border() {
     let board = Board::new("8/8/8/8/3R4/8/8/8 w - - 0 1");
-    let moves = board.possible_wr();
+    let mut moves = MoveList::new();
+    board.possible_rooks::<true>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 27, to: 24 },
-        Move::Normal { from: 27, to: 25 },
-        Move::Normal { from: 27, to: 26 },
-        Move::Normal { from: 27, to: 28 },
-        Move::Normal { from: 27, to: 29 },
-        Move::Normal { from: 27, to: 30 },
-        Move::Normal { from: 27, to: 31 },
-        Move::Normal { from: 27, to: 3 },
-        Move::Normal { from: 27, to: 11 },
-        Move::Normal { from: 27, to: 19 },
-        Move::Normal { from: 27, to: 35 },
-        Move::Normal { from: 27, to: 43 },
-        Move::Normal { from: 27, to: 51 },
-        Move::Normal { from: 27, to: 59 },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::A4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::G4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::H4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D1,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D7,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D8,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2222,13 +4273,29 @@ fn w_rook_move_border() {
 #[test]
 fn w_rook_move_block() {
     let board = Board::new("8/3P4/8/8/P2R1P2/3P4/8/8 w - - 0 1");
-    let moves = board.possible_wr();
+    let mut moves = MoveList::new();
+    board.possible_rooks::<true>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 27, to: 25 },
-        Move::Normal { from: 27, to: 26 },
-        Move::Normal { from: 27, to: 28 },
-        Move::Normal { from: 27, to: 35 },
-        Move::Normal { from: 27, to: 43 },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D6,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2239,17 +4306,45 @@ fn w_rook_move_block() {
 #[test]
 fn w_rook_move_capture() {
     let board = Board::new("8/3p4/8/8/p2R1p2/3p4/8/8 w - - 0 1");
-    let moves = board.possible_wr();
+    let mut moves = MoveList::new();
+    board.possible_rooks::<true>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 27, to: 24 },
-        Move::Normal { from: 27, to: 25 },
-        Move::Normal { from: 27, to: 26 },
-        Move::Normal { from: 27, to: 28 },
-        Move::Normal { from: 27, to: 29 },
-        Move::Normal { from: 27, to: 19 },
-        Move::Normal { from: 27, to: 35 },
-        Move::Normal { from: 27, to: 43 },
-        Move::Normal { from: 27, to: 51 },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::A4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D7,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2260,22 +4355,65 @@ fn w_rook_move_capture() {
 #[test]
 fn b_rook_move_border() {
     let board = Board::new("8/8/8/8/3r4/8/8/8 w - - 0 1");
-    let moves = board.possible_br();
+    let mut moves = MoveList::new();
+    board.possible_rooks::<false>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 27, to: 24 },
-        Move::Normal { from: 27, to: 25 },
-        Move::Normal { from: 27, to: 26 },
-        Move::Normal { from: 27, to: 28 },
-        Move::Normal { from: 27, to: 29 },
-        Move::Normal { from: 27, to: 30 },
-        Move::Normal { from: 27, to: 31 },
-        Move::Normal { from: 27, to: 3 },
-        Move::Normal { from: 27, to: 11 },
-        Move::Normal { from: 27, to: 19 },
-        Move::Normal { from: 27, to: 35 },
-        Move::Normal { from: 27, to: 43 },
-        Move::Normal { from: 27, to: 51 },
-        Move::Normal { from: 27, to: 59 },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::A4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::G4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::H4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D1,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D7,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D8,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2286,13 +4424,29 @@ fn b_rook_move_border() {
 #[test]
 fn b_rook_move_block() {
     let board = Board::new("8/3p4/8/8/p2r1p2/3p4/8/8 w - - 0 1");
-    let moves = board.possible_br();
+    let mut moves = MoveList::new();
+    board.possible_rooks::<false>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 27, to: 25 },
-        Move::Normal { from: 27, to: 26 },
-        Move::Normal { from: 27, to: 28 },
-        Move::Normal { from: 27, to: 35 },
-        Move::Normal { from: 27, to: 43 },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D6,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2303,17 +4457,45 @@ fn b_rook_move_block() {
 #[test]
 fn b_rook_move_capture() {
     let board = Board::new("8/3P4/8/8/P2r1P2/3P4/8/8 w - - 0 1");
-    let moves = board.possible_br();
+    let mut moves = MoveList::new();
+    board.possible_rooks::<false>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 27, to: 24 },
-        Move::Normal { from: 27, to: 25 },
-        Move::Normal { from: 27, to: 26 },
-        Move::Normal { from: 27, to: 28 },
-        Move::Normal { from: 27, to: 29 },
-        Move::Normal { from: 27, to: 19 },
-        Move::Normal { from: 27, to: 35 },
-        Move::Normal { from: 27, to: 43 },
-        Move::Normal { from: 27, to: 51 },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::A4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D7,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2324,7 +4506,8 @@ fn b_rook_move_capture() {
 #[test]
 fn overflow() {
     let board = Board::new("8/8/8/8/8/8/8/B7 w - - 0 1");
-    let moves = board.possible_wb();
+    let mut moves = MoveList::new();
+    board.possible_bishops::<true>(&mut moves, !0);
 
     assert_eq!(moves.len(), 7);
 }
@@ -2332,21 +4515,61 @@ fn overflow() {
 #[test]
 fn w_bishop_move_border() {
     let board = Board::new("8/8/8/8/3B4/8/8/8 w - - 0 1");
-    let moves = board.possible_wb();
+    let mut moves = MoveList::new();
+    board.possible_bishops::<true>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 27, to: 0 },
-        Move::Normal { from: 27, to: 9 },
-        Move::Normal { from: 27, to: 18 },
-        Move::Normal { from: 27, to: 36 },
-        Move::Normal { from: 27, to: 45 },
-        Move::Normal { from: 27, to: 54 },
-        Move::Normal { from: 27, to: 63 },
-        Move::Normal { from: 27, to: 48 },
-        Move::Normal { from: 27, to: 41 },
-        Move::Normal { from: 27, to: 34 },
-        Move::Normal { from: 27, to: 20 },
-        Move::Normal { from: 27, to: 13 },
-        Move::Normal { from: 27, to: 6 },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::A1,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::G7,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::H8,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::A7,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::G1,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2357,12 +4580,25 @@ fn w_bishop_move_border() {
 #[test]
 fn w_bishop_move_block() {
     let board = Board::new("8/8/1P3P2/8/3B4/4P3/8/P7 w - - 0 1");
-    let moves = board.possible_wb();
+    let mut moves = MoveList::new();
+    board.possible_bishops::<true>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 27, to: 9 },
-        Move::Normal { from: 27, to: 18 },
-        Move::Normal { from: 27, to: 36 },
-        Move::Normal { from: 27, to: 34 },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C5,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2373,16 +4609,41 @@ fn w_bishop_move_block() {
 #[test]
 fn w_bishop_move_capture() {
     let board = Board::new("8/8/1p3p2/8/3B4/4p3/8/p7 w - - 0 1");
-    let moves = board.possible_wb();
+    let mut moves = MoveList::new();
+    board.possible_bishops::<true>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 27, to: 0 },
-        Move::Normal { from: 27, to: 9 },
-        Move::Normal { from: 27, to: 18 },
-        Move::Normal { from: 27, to: 36 },
-        Move::Normal { from: 27, to: 45 },
-        Move::Normal { from: 27, to: 41 },
-        Move::Normal { from: 27, to: 34 },
-        Move::Normal { from: 27, to: 20 },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::A1,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E3,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2393,21 +4654,61 @@ fn w_bishop_move_capture() {
 #[test]
 fn b_bishop_move_border() {
     let board = Board::new("8/8/8/8/3b4/8/8/8 w - - 0 1");
-    let moves = board.possible_bb();
+    let mut moves = MoveList::new();
+    board.possible_bishops::<false>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 27, to: 0 },
-        Move::Normal { from: 27, to: 9 },
-        Move::Normal { from: 27, to: 18 },
-        Move::Normal { from: 27, to: 36 },
-        Move::Normal { from: 27, to: 45 },
-        Move::Normal { from: 27, to: 54 },
-        Move::Normal { from: 27, to: 63 },
-        Move::Normal { from: 27, to: 48 },
-        Move::Normal { from: 27, to: 41 },
-        Move::Normal { from: 27, to: 34 },
-        Move::Normal { from: 27, to: 20 },
-        Move::Normal { from: 27, to: 13 },
-        Move::Normal { from: 27, to: 6 },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::A1,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::G7,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::H8,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::A7,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::G1,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2418,12 +4719,25 @@ fn b_bishop_move_border() {
 #[test]
 fn b_bishop_move_block() {
     let board = Board::new("8/8/1p3p2/8/3b4/4p3/8/p7 w - - 0 1");
-    let moves = board.possible_bb();
+    let mut moves = MoveList::new();
+    board.possible_bishops::<false>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 27, to: 9 },
-        Move::Normal { from: 27, to: 18 },
-        Move::Normal { from: 27, to: 36 },
-        Move::Normal { from: 27, to: 34 },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C5,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2434,16 +4748,41 @@ fn b_bishop_move_block() {
 #[test]
 fn b_bishop_move_capture() {
     let board = Board::new("8/8/1P3P2/8/3b4/4P3/8/P7 w - - 0 1");
-    let moves = board.possible_bb();
+    let mut moves = MoveList::new();
+    board.possible_bishops::<false>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 27, to: 0 },
-        Move::Normal { from: 27, to: 9 },
-        Move::Normal { from: 27, to: 18 },
-        Move::Normal { from: 27, to: 36 },
-        Move::Normal { from: 27, to: 45 },
-        Move::Normal { from: 27, to: 41 },
-        Move::Normal { from: 27, to: 34 },
-        Move::Normal { from: 27, to: 20 },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::A1,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E3,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2454,35 +4793,117 @@ fn b_bishop_move_capture() {
 #[test]
 fn w_queen_move_border() {
     let board = Board::new("8/8/8/8/3Q4/8/8/8 w - - 0 1");
-    let moves = board.possible_wq();
+    let mut moves = MoveList::new();
+    board.possible_queens::<true>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 27, to: 0 },
-        Move::Normal { from: 27, to: 9 },
-        Move::Normal { from: 27, to: 18 },
-        Move::Normal { from: 27, to: 36 },
-        Move::Normal { from: 27, to: 45 },
-        Move::Normal { from: 27, to: 54 },
-        Move::Normal { from: 27, to: 63 },
-        Move::Normal { from: 27, to: 48 },
-        Move::Normal { from: 27, to: 41 },
-        Move::Normal { from: 27, to: 34 },
-        Move::Normal { from: 27, to: 20 },
-        Move::Normal { from: 27, to: 13 },
-        Move::Normal { from: 27, to: 6 },
-        Move::Normal { from: 27, to: 24 },
-        Move::Normal { from: 27, to: 25 },
-        Move::Normal { from: 27, to: 26 },
-        Move::Normal { from: 27, to: 28 },
-        Move::Normal { from: 27, to: 29 },
-        Move::Normal { from: 27, to: 30 },
-        Move::Normal { from: 27, to: 31 },
-        Move::Normal { from: 27, to: 3 },
-        Move::Normal { from: 27, to: 11 },
-        Move::Normal { from: 27, to: 19 },
-        Move::Normal { from: 27, to: 35 },
-        Move::Normal { from: 27, to: 43 },
-        Move::Normal { from: 27, to: 51 },
-        Move::Normal { from: 27, to: 59 },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::A1,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::G7,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::H8,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::A7,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::G1,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::A4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::G4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::H4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D1,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D7,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D8,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2493,17 +4914,45 @@ fn w_queen_move_border() {
 #[test]
 fn w_queen_move_block() {
     let board = Board::new("8/3P4/1P3P2/8/P2Q1P2/3PP3/8/P7 w - - 0 1");
-    let moves = board.possible_wq();
+    let mut moves = MoveList::new();
+    board.possible_queens::<true>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 27, to: 25 },
-        Move::Normal { from: 27, to: 26 },
-        Move::Normal { from: 27, to: 28 },
-        Move::Normal { from: 27, to: 35 },
-        Move::Normal { from: 27, to: 43 },
-        Move::Normal { from: 27, to: 9 },
-        Move::Normal { from: 27, to: 18 },
-        Move::Normal { from: 27, to: 36 },
-        Move::Normal { from: 27, to: 34 },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C5,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2514,25 +4963,77 @@ fn w_queen_move_block() {
 #[test]
 fn w_queen_move_capture() {
     let board = Board::new("8/3p4/1p3p2/8/p2Q1p2/3pp3/8/p7 w - - 0 1");
-    let moves = board.possible_wq();
+    let mut moves = MoveList::new();
+    board.possible_queens::<true>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 27, to: 24 },
-        Move::Normal { from: 27, to: 25 },
-        Move::Normal { from: 27, to: 26 },
-        Move::Normal { from: 27, to: 28 },
-        Move::Normal { from: 27, to: 29 },
-        Move::Normal { from: 27, to: 19 },
-        Move::Normal { from: 27, to: 35 },
-        Move::Normal { from: 27, to: 43 },
-        Move::Normal { from: 27, to: 51 },
-        Move::Normal { from: 27, to: 0 },
-        Move::Normal { from: 27, to: 9 },
-        Move::Normal { from: 27, to: 18 },
-        Move::Normal { from: 27, to: 36 },
-        Move::Normal { from: 27, to: 45 },
-        Move::Normal { from: 27, to: 41 },
-        Move::Normal { from: 27, to: 34 },
-        Move::Normal { from: 27, to: 20 },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::A4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D7,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::A1,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E3,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2543,35 +5044,117 @@ fn w_queen_move_capture() {
 #[test]
 fn b_queen_move_border() {
     let board = Board::new("8/8/8/8/3q4/8/8/8 w - - 0 1");
-    let moves = board.possible_bq();
+    let mut moves = MoveList::new();
+    board.possible_queens::<false>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 27, to: 0 },
-        Move::Normal { from: 27, to: 9 },
-        Move::Normal { from: 27, to: 18 },
-        Move::Normal { from: 27, to: 36 },
-        Move::Normal { from: 27, to: 45 },
-        Move::Normal { from: 27, to: 54 },
-        Move::Normal { from: 27, to: 63 },
-        Move::Normal { from: 27, to: 48 },
-        Move::Normal { from: 27, to: 41 },
-        Move::Normal { from: 27, to: 34 },
-        Move::Normal { from: 27, to: 20 },
-        Move::Normal { from: 27, to: 13 },
-        Move::Normal { from: 27, to: 6 },
-        Move::Normal { from: 27, to: 24 },
-        Move::Normal { from: 27, to: 25 },
-        Move::Normal { from: 27, to: 26 },
-        Move::Normal { from: 27, to: 28 },
-        Move::Normal { from: 27, to: 29 },
-        Move::Normal { from: 27, to: 30 },
-        Move::Normal { from: 27, to: 31 },
-        Move::Normal { from: 27, to: 3 },
-        Move::Normal { from: 27, to: 11 },
-        Move::Normal { from: 27, to: 19 },
-        Move::Normal { from: 27, to: 35 },
-        Move::Normal { from: 27, to: 43 },
-        Move::Normal { from: 27, to: 51 },
-        Move::Normal { from: 27, to: 59 },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::A1,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::G7,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::H8,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::A7,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::G1,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::A4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::G4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::H4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D1,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D7,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D8,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2582,17 +5165,45 @@ fn b_queen_move_border() {
 #[test]
 fn b_queen_move_block() {
     let board = Board::new("8/3p4/1p3p2/8/p2q1p2/3pp3/8/p7 w - - 0 1");
-    let moves = board.possible_bq();
+    let mut moves = MoveList::new();
+    board.possible_queens::<false>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 27, to: 25 },
-        Move::Normal { from: 27, to: 26 },
-        Move::Normal { from: 27, to: 28 },
-        Move::Normal { from: 27, to: 35 },
-        Move::Normal { from: 27, to: 43 },
-        Move::Normal { from: 27, to: 9 },
-        Move::Normal { from: 27, to: 18 },
-        Move::Normal { from: 27, to: 36 },
-        Move::Normal { from: 27, to: 34 },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C5,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2603,25 +5214,77 @@ fn b_queen_move_block() {
 #[test]
 fn b_queen_move_capture() {
     let board = Board::new("8/3P4/1P3P2/8/P2q1P2/3PP3/8/P7 w - - 0 1");
-    let moves = board.possible_bq();
+    let mut moves = MoveList::new();
+    board.possible_queens::<false>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 27, to: 24 },
-        Move::Normal { from: 27, to: 25 },
-        Move::Normal { from: 27, to: 26 },
-        Move::Normal { from: 27, to: 28 },
-        Move::Normal { from: 27, to: 29 },
-        Move::Normal { from: 27, to: 19 },
-        Move::Normal { from: 27, to: 35 },
-        Move::Normal { from: 27, to: 43 },
-        Move::Normal { from: 27, to: 51 },
-        Move::Normal { from: 27, to: 0 },
-        Move::Normal { from: 27, to: 9 },
-        Move::Normal { from: 27, to: 18 },
-        Move::Normal { from: 27, to: 36 },
-        Move::Normal { from: 27, to: 45 },
-        Move::Normal { from: 27, to: 41 },
-        Move::Normal { from: 27, to: 34 },
-        Move::Normal { from: 27, to: 20 },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::A4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D7,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::A1,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E3,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2632,16 +5295,41 @@ fn b_queen_move_capture() {
 #[test]
 fn w_knight_move() {
     let board = Board::new("8/8/8/8/3N4/8/8/8 w - - 0 1");
-    let moves = board.possible_wn();
+    let mut moves = MoveList::new();
+    board.possible_knights::<true>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 27, to: 10 },
-        Move::Normal { from: 27, to: 12 },
-        Move::Normal { from: 27, to: 17 },
-        Move::Normal { from: 27, to: 21 },
-        Move::Normal { from: 27, to: 33 },
-        Move::Normal { from: 27, to: 37 },
-        Move::Normal { from: 27, to: 42 },
-        Move::Normal { from: 27, to: 44 },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E6,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2653,16 +5341,41 @@ fn w_knight_move() {
 #[test]
 fn w_knight_capture() {
     let board = Board::new("8/8/2p1p3/1p3p2/3N4/1p3p2/2p1p3/8 w - - 0 1");
-    let moves = board.possible_wn();
+    let mut moves = MoveList::new();
+    board.possible_knights::<true>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 27, to: 10 },
-        Move::Normal { from: 27, to: 12 },
-        Move::Normal { from: 27, to: 17 },
-        Move::Normal { from: 27, to: 21 },
-        Move::Normal { from: 27, to: 33 },
-        Move::Normal { from: 27, to: 37 },
-        Move::Normal { from: 27, to: 42 },
-        Move::Normal { from: 27, to: 44 },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E6,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2673,7 +5386,8 @@ fn w_knight_capture() {
 #[test]
 fn w_knight_block() {
     let board = Board::new("8/8/2P1P3/1P3P2/3N4/1P3P2/2P1P3/8 w - - 0 1");
-    let moves = board.possible_wn();
+    let mut moves = MoveList::new();
+    board.possible_knights::<true>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2684,16 +5398,41 @@ fn w_knight_block() {
 #[test]
 fn w_knight_border() {
     let board = Board::new("N6N/8/8/8/8/8/8/N6N w - - 0 1");
-    let moves = board.possible_wn();
+    let mut moves = MoveList::new();
+    board.possible_knights::<true>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 0, to: 10 },
-        Move::Normal { from: 0, to: 17 },
-        Move::Normal { from: 7, to: 13 },
-        Move::Normal { from: 7, to: 22 },
-        Move::Normal { from: 56, to: 41 },
-        Move::Normal { from: 56, to: 50 },
-        Move::Normal { from: 63, to: 46 },
-        Move::Normal { from: 63, to: 53 },
+        Move::Normal {
+            from: Square::A1,
+            to: Square::C2,
+        },
+        Move::Normal {
+            from: Square::A1,
+            to: Square::B3,
+        },
+        Move::Normal {
+            from: Square::H1,
+            to: Square::F2,
+        },
+        Move::Normal {
+            from: Square::H1,
+            to: Square::G3,
+        },
+        Move::Normal {
+            from: Square::A8,
+            to: Square::B6,
+        },
+        Move::Normal {
+            from: Square::A8,
+            to: Square::C7,
+        },
+        Move::Normal {
+            from: Square::H8,
+            to: Square::G6,
+        },
+        Move::Normal {
+            from: Square::H8,
+            to: Square::F7,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2704,16 +5443,41 @@ fn w_knight_border() {
 #[test]
 fn b_knight_move() {
     let board = Board::new("8/8/8/8/3n4/8/8/8 w - - 0 1");
-    let moves = board.possible_bn();
+    let mut moves = MoveList::new();
+    board.possible_knights::<false>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 27, to: 10 },
-        Move::Normal { from: 27, to: 12 },
-        Move::Normal { from: 27, to: 17 },
-        Move::Normal { from: 27, to: 21 },
-        Move::Normal { from: 27, to: 33 },
-        Move::Normal { from: 27, to: 37 },
-        Move::Normal { from: 27, to: 42 },
-        Move::Normal { from: 27, to: 44 },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E6,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2724,16 +5488,41 @@ fn b_knight_move() {
 #[test]
 fn b_knight_capture() {
     let board = Board::new("8/8/2P1P3/1P3P2/3n4/1P3P2/2P1P3/8 w - - 0 1");
-    let moves = board.possible_bn();
+    let mut moves = MoveList::new();
+    board.possible_knights::<false>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 27, to: 10 },
-        Move::Normal { from: 27, to: 12 },
-        Move::Normal { from: 27, to: 17 },
-        Move::Normal { from: 27, to: 21 },
-        Move::Normal { from: 27, to: 33 },
-        Move::Normal { from: 27, to: 37 },
-        Move::Normal { from: 27, to: 42 },
-        Move::Normal { from: 27, to: 44 },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E2,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::B5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::F5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C6,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E6,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2744,7 +5533,8 @@ fn b_knight_capture() {
 #[test]
 fn b_knight_block() {
     let board = Board::new("8/8/2p1p3/1p3p2/3n4/1p3p2/2p1p3/8 w - - 0 1");
-    let moves = board.possible_bn();
+    let mut moves = MoveList::new();
+    board.possible_knights::<false>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2755,16 +5545,41 @@ fn b_knight_block() {
 #[test]
 fn b_knight_border() {
     let board = Board::new("n6n/8/8/8/8/8/8/n6n w - - 0 1");
-    let moves = board.possible_bn();
+    let mut moves = MoveList::new();
+    board.possible_knights::<false>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 0, to: 10 },
-        Move::Normal { from: 0, to: 17 },
-        Move::Normal { from: 7, to: 13 },
-        Move::Normal { from: 7, to: 22 },
-        Move::Normal { from: 56, to: 41 },
-        Move::Normal { from: 56, to: 50 },
-        Move::Normal { from: 63, to: 46 },
-        Move::Normal { from: 63, to: 53 },
+        Move::Normal {
+            from: Square::A1,
+            to: Square::C2,
+        },
+        Move::Normal {
+            from: Square::A1,
+            to: Square::B3,
+        },
+        Move::Normal {
+            from: Square::H1,
+            to: Square::F2,
+        },
+        Move::Normal {
+            from: Square::H1,
+            to: Square::G3,
+        },
+        Move::Normal {
+            from: Square::A8,
+            to: Square::B6,
+        },
+        Move::Normal {
+            from: Square::A8,
+            to: Square::C7,
+        },
+        Move::Normal {
+            from: Square::H8,
+            to: Square::G6,
+        },
+        Move::Normal {
+            from: Square::H8,
+            to: Square::F7,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2775,16 +5590,41 @@ fn b_knight_border() {
 #[test]
 fn w_king_move_capture() {
     let board = Board::new("8/8/8/2p1p3/3K4/2p1p3/8/8 w - - 0 1");
-    let moves = board.possible_wk();
+    let mut moves = MoveList::new();
+    board.possible_king::<true>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 27, to: 18 },
-        Move::Normal { from: 27, to: 19 },
-        Move::Normal { from: 27, to: 20 },
-        Move::Normal { from: 27, to: 26 },
-        Move::Normal { from: 27, to: 28 },
-        Move::Normal { from: 27, to: 34 },
-        Move::Normal { from: 27, to: 35 },
-        Move::Normal { from: 27, to: 36 },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E5,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2795,7 +5635,8 @@ fn w_king_move_capture() {
 #[test]
 fn w_king_block() {
     let board = Board::new("8/8/8/2PPP3/2PKP3/2PPP3/8/8 w - - 0 1");
-    let moves = board.possible_wk();
+    let mut moves = MoveList::new();
+    board.possible_king::<true>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2806,16 +5647,41 @@ fn w_king_block() {
 #[test]
 fn b_king_move_capture() {
     let board = Board::new("8/8/8/2P1P3/3k4/2P1P3/8/8 w - - 0 1");
-    let moves = board.possible_bk();
+    let mut moves = MoveList::new();
+    board.possible_king::<false>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![
-        Move::Normal { from: 27, to: 18 },
-        Move::Normal { from: 27, to: 19 },
-        Move::Normal { from: 27, to: 20 },
-        Move::Normal { from: 27, to: 26 },
-        Move::Normal { from: 27, to: 28 },
-        Move::Normal { from: 27, to: 34 },
-        Move::Normal { from: 27, to: 35 },
-        Move::Normal { from: 27, to: 36 },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E3,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E4,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::C5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::D5,
+        },
+        Move::Normal {
+            from: Square::D4,
+            to: Square::E5,
+        },
     ];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2826,7 +5692,8 @@ fn b_king_move_capture() {
 #[test]
 fn b_king_block() {
     let board = Board::new("8/8/8/2ppp3/2pkp3/2ppp3/8/8 w - - 0 1");
-    let moves = board.possible_bk();
+    let mut moves = MoveList::new();
+    board.possible_king::<false>(&mut moves, !0);
     let correct_moves: Vec<Move> = vec![];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2837,17 +5704,18 @@ fn b_king_block() {
 #[test]
 fn castling_w_kq() {
     let board = Board::new("8/8/8/8/8/8/8/R3K2R w KQ - 0 1");
-    let moves = board.possible_wc();
+    let mut moves = MoveList::new();
+    board.possible_castles::<true>(&mut moves);
     let correct_moves: Vec<Move> = vec![
         Move::Castle {
-            from: 4,
-            to: 6,
-            rook: 7,
+            from: Square::E1,
+            to: Square::G1,
+            rook: Square::H1,
         },
         Move::Castle {
-            from: 4,
-            to: 2,
-            rook: 0,
+            from: Square::E1,
+            to: Square::C1,
+            rook: Square::A1,
         },
     ];
     assert_eq!(moves.len(), correct_moves.len());
@@ -2859,11 +5727,12 @@ fn castling_w_kq() {
 #[test]
 fn castling_w_k() {
     let board = Board::new("8/8/8/8/8/8/8/R3K2R w K - 0 1");
-    let moves = board.possible_wc();
+    let mut moves = MoveList::new();
+    board.possible_castles::<true>(&mut moves);
     let correct_moves: Vec<Move> = vec![Move::Castle {
-        from: 4,
-        to: 6,
-        rook: 7,
+        from: Square::E1,
+        to: Square::G1,
+        rook: Square::H1,
     }];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2874,11 +5743,12 @@ fn castling_w_k() {
 #[test]
 fn castling_w_q() {
     let board = Board::new("8/8/8/8/8/8/8/R3K2R w Q - 0 1");
-    let moves = board.possible_wc();
+    let mut moves = MoveList::new();
+    board.possible_castles::<true>(&mut moves);
     let correct_moves: Vec<Move> = vec![Move::Castle {
-        from: 4,
-        to: 2,
-        rook: 0,
+        from: Square::E1,
+        to: Square::C1,
+        rook: Square::A1,
     }];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2890,7 +5760,8 @@ fn castling_w_q() {
 fn castling_w_unsafe() {
     let board = Board::new("2r3r1/8/8/8/8/8/8/R3K2R w KQ - 0 1");
 
-    let moves = board.possible_wc();
+    let mut moves = MoveList::new();
+    board.possible_castles::<true>(&mut moves);
     let correct_moves: Vec<Move> = vec![];
     assert_eq!(moves.len(), correct_moves.len());
 }
@@ -2898,7 +5769,8 @@ fn castling_w_unsafe() {
 #[test]
 fn castling_w_checked() {
     let board = Board::new("4r3/8/8/8/8/8/8/R3K2R w KQ - 0 1");
-    let moves = board.possible_wc();
+    let mut moves = MoveList::new();
+    board.possible_castles::<true>(&mut moves);
     let correct_moves: Vec<Move> = vec![];
     assert_eq!(moves.len(), correct_moves.len());
 }
@@ -2906,7 +5778,8 @@ fn castling_w_checked() {
 #[test]
 fn castling_w_blocked() {
     let board = Board::new("8/8/8/8/8/8/8/R2PKP1R w KQ - 0 1");
-    let moves = board.possible_wc();
+    let mut moves = MoveList::new();
+    board.possible_castles::<true>(&mut moves);
     let correct_moves: Vec<Move> = vec![];
     assert_eq!(moves.len(), correct_moves.len());
 }
@@ -2914,17 +5787,18 @@ fn castling_w_blocked() {
 #[test]
 fn castling_b_kq() {
     let board = Board::new("r3k2r/8/8/8/8/8/8/8 w kq - 0 1");
-    let moves = board.possible_bc();
+    let mut moves = MoveList::new();
+    board.possible_castles::<false>(&mut moves);
     let correct_moves: Vec<Move> = vec![
         Move::Castle {
-            from: 60,
-            to: 62,
-            rook: 63,
+            from: Square::E8,
+            to: Square::G8,
+            rook: Square::H8,
         },
         Move::Castle {
-            from: 60,
-            to: 58,
-            rook: 56,
+            from: Square::E8,
+            to: Square::C8,
+            rook: Square::A8,
         },
     ];
     assert_eq!(moves.len(), correct_moves.len());
@@ -2936,11 +5810,12 @@ fn castling_b_kq() {
 #[test]
 fn castling_b_k() {
     let board = Board::new("r3k2r/8/8/8/8/8/8/8 w k - 0 1");
-    let moves = board.possible_bc();
+    let mut moves = MoveList::new();
+    board.possible_castles::<false>(&mut moves);
     let correct_moves: Vec<Move> = vec![Move::Castle {
-        from: 60,
-        to: 62,
-        rook: 63,
+        from: Square::E8,
+        to: Square::G8,
+        rook: Square::H8,
     }];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2951,11 +5826,12 @@ fn castling_b_k() {
 #[test]
 fn castling_b_q() {
     let board = Board::new("r3k2r/8/8/8/8/8/8/8 w q - 0 1");
-    let moves = board.possible_bc();
+    let mut moves = MoveList::new();
+    board.possible_castles::<false>(&mut moves);
     let correct_moves: Vec<Move> = vec![Move::Castle {
-        from: 60,
-        to: 58,
-        rook: 56,
+        from: Square::E8,
+        to: Square::C8,
+        rook: Square::A8,
     }];
     assert_eq!(moves.len(), correct_moves.len());
     for m in moves {
@@ -2966,7 +5842,8 @@ fn castling_b_q() {
 #[test]
 fn castling_b_unsafe() {
     let board = Board::new("r3k2r/8/8/8/8/8/8/2R2R2 w q - 0 1");
-    let moves = board.possible_bc();
+    let mut moves = MoveList::new();
+    board.possible_castles::<false>(&mut moves);
     let correct_moves: Vec<Move> = vec![];
     assert_eq!(moves.len(), correct_moves.len());
 }
@@ -2974,7 +5851,8 @@ fn castling_b_unsafe() {
 #[test]
 fn castling_b_checked() {
     let board = Board::new("r3k2r/8/8/8/8/8/8/4R3 w q - 0 1");
-    let moves = board.possible_bc();
+    let mut moves = MoveList::new();
+    board.possible_castles::<false>(&mut moves);
     let correct_moves: Vec<Move> = vec![];
     assert_eq!(moves.len(), correct_moves.len());
 }
@@ -2982,7 +5860,8 @@ fn castling_b_checked() {
 #[test]
 fn castling_b_blocked() {
     let board = Board::new("r2pkp1r/8/8/8/8/8/8/8 w q - 0 1");
-    let moves = board.possible_bc();
+    let mut moves = MoveList::new();
+    board.possible_castles::<false>(&mut moves);
     let correct_moves: Vec<Move> = vec![];
     assert_eq!(moves.len(), correct_moves.len());
 }
@@ -2990,7 +5869,7 @@ fn castling_b_blocked() {
 #[test]
 fn w_unsafe_squares() {
     let board = Board::new("8/4r3/3n2b1/3p2n1/4K3/8/6q1/8 w - - 0 1");
-    let unsafe_squares: u64 = board.unsafe_w();
+    let unsafe_squares: u64 = board.unsafe_squares::<true>();
     let correct_unsafe_squares: u64 = 1508443033184550880;
     assert_eq!(unsafe_squares, correct_unsafe_squares);
 }
@@ -2998,7 +5877,1010 @@ fn w_unsafe_squares() {
 #[test]
 fn b_unsafe_squares() {
     let board = Board::new("8/4R3/3N2B1/6N1/4k3/3P4/6Q1/8 w - - 0 1");
-    let unsafe_squares: u64 = board.unsafe_b();
+    let unsafe_squares: u64 = board.unsafe_squares::<false>();
     let correct_unsafe_squares: u64 = 1508443033184550880;
     assert_eq!(unsafe_squares, correct_unsafe_squares);
 }
+
+#[test]
+fn leaper_attack_tables() {
+    // knight on a1 attacks b3 and c2
+    assert_eq!(KNIGHT_ATTACKS[0], (1u64 << 17) | (1u64 << 10));
+    // king on h8 attacks g8, g7 and h7
+    assert_eq!(KING_ATTACKS[63], (1u64 << 62) | (1u64 << 54) | (1u64 << 55));
+    // white pawn on a2 attacks b3, black pawn on h7 attacks g6
+    assert_eq!(PAWN_ATTACKS[0][8], 1u64 << 17);
+    assert_eq!(PAWN_ATTACKS[1][55], 1u64 << 46);
+}
+
+#[test]
+fn set_bits_iteration() {
+    let positions: Vec<u8> = SetBits((1u64 << 63) | (1u64 << 12) | 1).collect();
+    assert_eq!(positions, vec![0, 12, 63]);
+    assert_eq!(SetBits(0).next(), None);
+}
+
+#[test]
+fn fen_round_trip() {
+    let fens = [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R b Kq - 3 17",
+    ];
+
+    for fen in fens {
+        assert_eq!(Board::from_fen(fen).unwrap().export_fen(), fen);
+    }
+}
+
+#[test]
+fn fen_from_str() {
+    let fen = "8/8/8/8/8/8/8/4K2R w K - 0 1";
+
+    let board: Board = fen.parse().unwrap();
+    assert_eq!(board.export_fen(), fen);
+
+    let board = Board::try_from(fen).unwrap();
+    assert_eq!(board.export_fen(), fen);
+}
+
+#[test]
+fn fen_missing_field() {
+    let error = Board::from_fen("8/8/8/8/8/8/8/8 w -").unwrap_err();
+    assert_eq!(
+        error,
+        FenError::new(FenField::EnPassant, 19, FenErrorKind::MissingField)
+    );
+
+    let error = Board::from_fen("").unwrap_err();
+    assert_eq!(
+        error,
+        FenError::new(FenField::Pieces, 0, FenErrorKind::MissingField)
+    );
+}
+
+#[test]
+fn fen_unexpected_field() {
+    let error = Board::from_fen("8/8/8/8/8/8/8/8 w - - 0 1 bm").unwrap_err();
+    assert_eq!(
+        error,
+        FenError::new(FenField::FullMove, 26, FenErrorKind::UnexpectedField)
+    );
+}
+
+#[test]
+fn fen_invalid_pieces() {
+    let error = Board::from_fen("8/8/8/8/8/8/8/8/8 w - - 0 1").unwrap_err();
+    assert_eq!(
+        error,
+        FenError::new(FenField::Pieces, 15, FenErrorKind::TooManyRanks)
+    );
+
+    let error = Board::from_fen("8/8/8/8/8/8/8 w - - 0 1").unwrap_err();
+    assert_eq!(
+        error,
+        FenError::new(FenField::Pieces, 13, FenErrorKind::TooFewRanks)
+    );
+
+    let error = Board::from_fen("8/8/3p5/8/8/8/8/8 w - - 0 1").unwrap_err();
+    assert_eq!(
+        error,
+        FenError::new(
+            FenField::Pieces,
+            6,
+            FenErrorKind::TooManySquares { rank: 5 }
+        )
+    );
+
+    let error = Board::from_fen("8/8/8/8/7/8/8/8 w - - 0 1").unwrap_err();
+    assert_eq!(
+        error,
+        FenError::new(FenField::Pieces, 9, FenErrorKind::TooFewSquares { rank: 3 })
+    );
+
+    let error = Board::from_fen("8/8/8/8/3x4/8/8/8 w - - 0 1").unwrap_err();
+    assert_eq!(
+        error,
+        FenError::new(FenField::Pieces, 9, FenErrorKind::UnknownPiece('x'))
+    );
+
+    let error = Board::from_fen("8/8/8/8/09/8/8/8 w - - 0 1").unwrap_err();
+    assert_eq!(
+        error,
+        FenError::new(FenField::Pieces, 8, FenErrorKind::UnknownPiece('0'))
+    );
+}
+
+#[test]
+fn fen_invalid_turn() {
+    let error = Board::from_fen("8/8/8/8/8/8/8/8 x - - 0 1").unwrap_err();
+    assert_eq!(
+        error,
+        FenError::new(FenField::Turn, 16, FenErrorKind::InvalidTurn)
+    );
+}
+
+#[test]
+fn fen_invalid_castling() {
+    let error = Board::from_fen("8/8/8/8/8/8/8/8 w KA - 0 1").unwrap_err();
+    assert_eq!(
+        error,
+        FenError::new(FenField::Castling, 19, FenErrorKind::InvalidCastling('A'))
+    );
+
+    let error = Board::from_fen("8/8/8/8/8/8/8/8 w KQK - 0 1").unwrap_err();
+    assert_eq!(
+        error,
+        FenError::new(FenField::Castling, 20, FenErrorKind::DuplicateCastling('K'))
+    );
+
+    let error = Board::from_fen("8/8/8/8/8/8/8/8 w K- - 0 1").unwrap_err();
+    assert_eq!(
+        error,
+        FenError::new(FenField::Castling, 19, FenErrorKind::InvalidCastling('-'))
+    );
+}
+
+#[test]
+fn fen_invalid_en_passant() {
+    for en_passant in ["z", "e", "e4", "i6", "e66"] {
+        let fen = format!("8/8/8/8/8/8/8/8 w - {} 0 1", en_passant);
+        let error = Board::from_fen(&fen).unwrap_err();
+        assert_eq!(
+            error,
+            FenError::new(FenField::EnPassant, 20, FenErrorKind::InvalidEnPassant)
+        );
+    }
+}
+
+#[test]
+fn fen_invalid_counters() {
+    let error = Board::from_fen("8/8/8/8/8/8/8/8 w - - x 1").unwrap_err();
+    assert_eq!(
+        error,
+        FenError::new(FenField::HalfMove, 22, FenErrorKind::InvalidCounter)
+    );
+
+    let error = Board::from_fen("8/8/8/8/8/8/8/8 w - - -1 1").unwrap_err();
+    assert_eq!(
+        error,
+        FenError::new(FenField::HalfMove, 22, FenErrorKind::InvalidCounter)
+    );
+
+    let error = Board::from_fen("8/8/8/8/8/8/8/8 w - - 0 0").unwrap_err();
+    assert_eq!(
+        error,
+        FenError::new(FenField::FullMove, 24, FenErrorKind::CounterOutOfRange)
+    );
+
+    let error = Board::from_fen("8/8/8/8/8/8/8/8 w - - 0 99999999999").unwrap_err();
+    assert_eq!(
+        error,
+        FenError::new(FenField::FullMove, 24, FenErrorKind::CounterOutOfRange)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Invalid FEN string")]
+fn fen_new_panics() {
+    Board::new("8/8/8/8/8/8/8/8 w - z 0 1");
+}
+
+#[test]
+fn validate_legal_positions() {
+    let fens = [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+    ];
+
+    for fen in fens {
+        assert_eq!(Board::new(fen).validate(), vec![]);
+        assert!(Board::from_fen_strict(fen).is_ok());
+    }
+}
+
+#[test]
+fn validate_king_count() {
+    let board = Board::new("4k3/8/8/8/8/8/8/8 w - - 0 1");
+    assert_eq!(
+        board.validate(),
+        vec![PositionIssue::KingCount {
+            white: true,
+            count: 0
+        }]
+    );
+
+    let board = Board::new("2kkk3/8/8/8/8/8/8/4K3 w - - 0 1");
+    assert_eq!(
+        board.validate(),
+        vec![PositionIssue::KingCount {
+            white: false,
+            count: 3
+        }]
+    );
+}
+
+#[test]
+fn validate_pawn_on_back_rank() {
+    let board = Board::new("4k2P/8/8/8/8/8/8/p3K3 w - - 0 1");
+    assert_eq!(
+        board.validate(),
+        vec![
+            PositionIssue::PawnOnBackRank { square: Square::A1 },
+            PositionIssue::PawnOnBackRank { square: Square::H8 },
+        ]
+    );
+}
+
+#[test]
+fn validate_castling_rights() {
+    let board = Board::new("r3k3/8/8/8/8/8/8/R4K1R w KQkq - 0 1");
+    assert_eq!(
+        board.validate(),
+        vec![
+            PositionIssue::InvalidCastlingRights {
+                white: true,
+                kingside: true
+            },
+            PositionIssue::InvalidCastlingRights {
+                white: true,
+                kingside: false
+            },
+            PositionIssue::InvalidCastlingRights {
+                white: false,
+                kingside: true
+            },
+        ]
+    );
+}
+
+#[test]
+fn validate_en_passant() {
+    let board = Board::new("4k3/8/8/4p3/8/8/8/4K3 w - e6 0 1");
+    assert_eq!(board.validate(), vec![]);
+
+    // no pawn on the square moved to
+    let board = Board::new("4k3/8/8/8/8/8/8/4K3 w - e6 0 1");
+    assert_eq!(
+        board.validate(),
+        vec![PositionIssue::InvalidEnPassant { square: Square::E6 }]
+    );
+
+    // square moved from is occupied
+    let board = Board::new("4k3/4n3/8/4p3/8/8/8/4K3 w - e6 0 1");
+    assert_eq!(
+        board.validate(),
+        vec![PositionIssue::InvalidEnPassant { square: Square::E6 }]
+    );
+
+    // wrong rank for the side to move
+    let board = Board::new("4k3/8/8/8/4P3/8/8/4K3 w - e3 0 1");
+    assert_eq!(
+        board.validate(),
+        vec![PositionIssue::InvalidEnPassant { square: Square::E3 }]
+    );
+}
+
+#[test]
+fn validate_opponent_in_check() {
+    let board = Board::new("4k3/8/8/8/8/8/8/4KR2 w - - 0 1");
+    assert_eq!(board.validate(), vec![]);
+
+    let board = Board::new("4k3/8/8/8/8/8/8/4R1K1 w - - 0 1");
+    assert_eq!(board.validate(), vec![PositionIssue::OpponentInCheck]);
+
+    let board = Board::new("4k3/8/8/8/8/3n4/8/4K3 b - - 0 1");
+    assert_eq!(board.validate(), vec![PositionIssue::OpponentInCheck]);
+}
+
+#[test]
+fn from_fen_strict_errors() {
+    let error = Board::from_fen_strict("8/8/8/8/8/8/8/8 w - - 0").unwrap_err();
+    assert!(matches!(error, PositionError::Fen(_)));
+
+    let error = Board::from_fen_strict("8/8/8/8/8/8/8/8 w - - 0 1").unwrap_err();
+    assert_eq!(
+        error,
+        PositionError::Illegal(vec![
+            PositionIssue::KingCount {
+                white: true,
+                count: 0
+            },
+            PositionIssue::KingCount {
+                white: false,
+                count: 0
+            },
+        ])
+    );
+}
+
+#[test]
+fn make_move_counters() {
+    let board = Board::new("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+
+    // pawn move
+    let board = board
+        .make_move(&Move::Normal {
+            from: Square::E2,
+            to: Square::E4,
+        })
+        .unwrap();
+    assert_eq!((board.halfmove(), board.fullmove()), (0, 1));
+
+    // knight move
+    let board = board
+        .make_move(&Move::Normal {
+            from: Square::G8,
+            to: Square::F6,
+        })
+        .unwrap();
+    assert_eq!((board.halfmove(), board.fullmove()), (1, 2));
+
+    // knight move
+    let board = board
+        .make_move(&Move::Normal {
+            from: Square::G1,
+            to: Square::F3,
+        })
+        .unwrap();
+    assert_eq!((board.halfmove(), board.fullmove()), (2, 2));
+
+    // knight captures pawn
+    let board = board
+        .make_move(&Move::Normal {
+            from: Square::F6,
+            to: Square::E4,
+        })
+        .unwrap();
+    assert_eq!((board.halfmove(), board.fullmove()), (0, 3));
+
+    assert_eq!(
+        board.export_fen(),
+        "rnbqkb1r/pppppppp/8/8/4n3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 0 3"
+    );
+}
+
+#[test]
+fn make_move_wide_counters() {
+    let board = Board::new("4k3/8/8/8/8/8/8/4K3 b - - 299 1000");
+
+    let board = board
+        .make_move(&Move::Normal {
+            from: Square::E8,
+            to: Square::D8,
+        })
+        .unwrap();
+    assert_eq!((board.halfmove(), board.fullmove()), (300, 1001));
+    assert_eq!(board.export_fen(), "3k4/8/8/8/8/8/8/4K3 w - - 300 1001");
+}
+
+#[test]
+fn hash_transposition() {
+    let board = Board::new("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+
+    let moved = board
+        .make_move(&Move::Normal {
+            from: Square::G1,
+            to: Square::F3,
+        })
+        .unwrap()
+        .make_move(&Move::Normal {
+            from: Square::G8,
+            to: Square::F6,
+        })
+        .unwrap()
+        .make_move(&Move::Normal {
+            from: Square::F3,
+            to: Square::G1,
+        })
+        .unwrap()
+        .make_move(&Move::Normal {
+            from: Square::F6,
+            to: Square::G8,
+        })
+        .unwrap();
+
+    assert_eq!(moved.hash(), board.hash());
+
+    let moved = board
+        .make_move(&Move::Normal {
+            from: Square::G1,
+            to: Square::F3,
+        })
+        .unwrap();
+    assert_ne!(moved.hash(), board.hash());
+}
+
+#[test]
+fn hash_side_and_castling() {
+    let white = Board::new("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+    let black = Board::new("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1");
+    let no_castling = Board::new("r3k2r/8/8/8/8/8/8/R3K2R w Kkq - 0 1");
+
+    assert_eq!(white.hash() ^ black.hash(), zobrist::SIDE);
+    assert_eq!(white.hash() ^ no_castling.hash(), zobrist::CASTLING[1]);
+}
+
+#[test]
+fn hash_en_passant_only_if_capturable() {
+    // no black pawn can capture on e3
+    let board = Board::new("4k3/8/8/8/4P3/8/8/4K3 b - e3 0 1");
+    let no_en_passant = Board::new("4k3/8/8/8/4P3/8/8/4K3 b - - 0 1");
+    assert_eq!(board.hash(), no_en_passant.hash());
+
+    // the black pawn on d4 can capture on e3
+    let board = Board::new("4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1");
+    let no_en_passant = Board::new("4k3/8/8/8/3pP3/8/8/4K3 b - - 0 1");
+    assert_eq!(board.hash() ^ no_en_passant.hash(), zobrist::EN_PASSANT[4]);
+}
+
+#[test]
+fn hash_matches_fen() {
+    let board = Board::new("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1");
+
+    for m in board.possible_white() {
+        if let Ok(new_board) = board.make_move(&m) {
+            assert_eq!(new_board.hash(), Board::new(&new_board.export_fen()).hash());
+        }
+    }
+}
+
+#[test]
+fn legal_double_check() {
+    let board = Board::new("4k3/8/8/8/8/5n2/8/R3K2r w - - 0 1");
+    let moves = board.legal_moves();
+    let correct_moves: Vec<Move> = vec![
+        Move::Normal {
+            from: Square::E1,
+            to: Square::E2,
+        },
+        Move::Normal {
+            from: Square::E1,
+            to: Square::F2,
+        },
+    ];
+    assert_eq!(moves.len(), correct_moves.len());
+    for m in moves {
+        assert!(correct_moves.contains(&m));
+    }
+}
+
+#[test]
+fn legal_pinned_piece() {
+    let board = Board::new("4k3/8/8/8/8/4r3/4R3/4K3 w - - 0 1");
+    let moves: Vec<Move> = board
+        .legal_moves()
+        .into_iter()
+        .filter(|m| {
+            matches!(
+                m,
+                Move::Normal {
+                    from: Square::E2,
+                    ..
+                }
+            )
+        })
+        .collect();
+    assert_eq!(
+        moves,
+        vec![Move::Normal {
+            from: Square::E2,
+            to: Square::E3
+        }]
+    );
+}
+
+#[test]
+fn legal_en_passant_discovered_check() {
+    let board = Board::new("8/8/8/KPp4r/8/8/8/6k1 w - c6 0 1");
+    assert!(!board
+        .legal_moves()
+        .iter()
+        .any(|m| matches!(m, Move::EnPassant { .. })));
+}
+
+#[test]
+fn uci_round_trip() {
+    let fens = [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 b kq - 0 1",
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+    ];
+
+    for fen in fens {
+        let board = Board::new(fen);
+
+        for m in board.legal_moves() {
+            assert_eq!(board.parse_uci_move(&m.to_uci()), Ok(m), "{}", fen);
+        }
+    }
+}
+
+#[test]
+fn uci_moves() {
+    let board = Board::new("r3k2r/1P6/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1");
+
+    assert_eq!(
+        board.parse_uci_move("e1g1"),
+        Ok(Move::Castle {
+            from: Square::E1,
+            to: Square::G1,
+            rook: Square::H1
+        })
+    );
+    assert_eq!(
+        board.parse_uci_move("e5d6"),
+        Ok(Move::EnPassant {
+            from: Square::E5,
+            to: Square::D6,
+            captured: Square::D5
+        })
+    );
+    assert_eq!(
+        board.parse_uci_move("b7a8n"),
+        Ok(Move::Promotion {
+            from: Square::B7,
+            to: Square::A8,
+            promotion: Piece::WhiteKnight
+        })
+    );
+    assert_eq!(
+        Move::Promotion {
+            from: Square::B7,
+            to: Square::B8,
+            promotion: Piece::WhiteQueen
+        }
+        .to_uci(),
+        "b7b8q"
+    );
+}
+
+#[test]
+fn uci_invalid_moves() {
+    let board = Board::new("r3k2r/1P6/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1");
+
+    for input in [
+        "", "e1", "e1g", "e1g1x", "e1g1qq", "i1g1", "e1g9", "b7a8k", "b7a8Q", "é1g1",
+    ] {
+        assert_eq!(
+            board.parse_uci_move(input),
+            Err(MoveParseError::Malformed(input.to_string()))
+        );
+    }
+
+    // a promotion without a piece, a piece that can not move there and a move of the opponent
+    for input in ["b7b8", "e1e3", "a8a7", "e5e6q"] {
+        assert_eq!(
+            board.parse_uci_move(input),
+            Err(MoveParseError::Illegal(input.to_string()))
+        );
+    }
+}
+
+#[test]
+fn san_round_trip() {
+    let fens = [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 b kq - 0 1",
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        "8/8/8/7k/8/Q7/8/Q1Q4K w - - 0 1",
+    ];
+
+    for fen in fens {
+        let board = Board::new(fen);
+
+        for m in board.legal_moves() {
+            assert_eq!(board.parse_san(&board.to_san(m)), Ok(m), "{}", fen);
+        }
+    }
+}
+
+#[test]
+fn san_moves() {
+    let board = Board::new("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1");
+    let san = |from: Square, to: Square| board.to_san(Move::Normal { from, to });
+
+    assert_eq!(san(Square::E2, Square::A6), "Bxa6");
+    assert_eq!(san(Square::D5, Square::E6), "dxe6");
+    assert_eq!(san(Square::C3, Square::B1), "Nb1");
+    assert_eq!(san(Square::E5, Square::F7), "Nxf7");
+    assert_eq!(san(Square::A1, Square::D1), "Rd1");
+    assert_eq!(
+        board.to_san(Move::Castle {
+            from: Square::E1,
+            to: Square::C1,
+            rook: Square::A1
+        }),
+        "O-O-O"
+    );
+
+    // disambiguation by file, by rank and by both
+    let board = Board::new("8/8/8/7k/8/Q7/8/Q1Q4K w - - 0 1");
+    let san = |from: Square, to: Square| board.to_san(Move::Normal { from, to });
+
+    assert_eq!(san(Square::C1, Square::B2), "Qcb2");
+    assert_eq!(san(Square::A3, Square::B2), "Q3b2");
+    assert_eq!(san(Square::A1, Square::B2), "Qa1b2");
+    assert_eq!(san(Square::A1, Square::A2), "Q1a2");
+    assert_eq!(san(Square::C1, Square::H6), "Qh6+");
+
+    // checkmate and promotion
+    let board = Board::new("6k1/1P3ppp/8/8/8/8/8/R5K1 w - - 0 1");
+
+    assert_eq!(
+        board.to_san(Move::Normal {
+            from: Square::A1,
+            to: Square::A8
+        }),
+        "Ra8#"
+    );
+    assert_eq!(
+        board.to_san(Move::Promotion {
+            from: Square::B7,
+            to: Square::B8,
+            promotion: Piece::WhiteKnight
+        }),
+        "b8=N"
+    );
+    assert_eq!(
+        board.to_san(Move::Promotion {
+            from: Square::B7,
+            to: Square::B8,
+            promotion: Piece::WhiteQueen
+        }),
+        "b8=Q#"
+    );
+}
+
+#[test]
+fn san_sloppy_moves() {
+    let board = Board::new("r3k2r/1P6/8/3pP3/8/2N5/8/R3K2R w KQkq d6 0 1");
+
+    let parse = |input: &str| board.parse_san(input).map(|m| board.to_san(m));
+
+    assert_eq!(parse("0-0"), Ok("O-O".to_string()));
+    assert_eq!(parse("O-O+"), Ok("O-O".to_string()));
+    assert_eq!(parse("O-O-O"), Ok("O-O-O".to_string()));
+    assert_eq!(parse("Nc3-d5"), Ok("Nxd5".to_string()));
+    assert_eq!(parse("Nc3xd5!?"), Ok("Nxd5".to_string()));
+    assert_eq!(parse("Nd5"), Ok("Nxd5".to_string()));
+    assert_eq!(parse("e5:d6"), Ok("exd6".to_string()));
+    assert_eq!(parse("exd6 e.p."), Ok("exd6".to_string()));
+    assert_eq!(parse("ed6"), Ok("exd6".to_string()));
+    assert_eq!(parse("e6+"), Ok("e6".to_string()));
+    assert_eq!(parse("bxa8Q"), Ok("bxa8=Q+".to_string()));
+    assert_eq!(parse("b8=q"), Ok("b8=Q+".to_string()));
+    assert_eq!(parse("b7b8r"), Ok("b8=R+".to_string()));
+}
+
+#[test]
+fn san_invalid_moves() {
+    let board = Board::new("r3k2r/1P6/8/3pP3/8/2N5/8/R3K2R w KQkq d6 0 1");
+
+    for input in ["", "N", "Nz3", "e9", "b8=K", "Nc3d5e", "e4e", "Pe2e3e4"] {
+        assert_eq!(
+            board.parse_san(input),
+            Err(MoveParseError::Malformed(input.to_string())),
+            "{}",
+            input
+        );
+    }
+
+    for input in ["e4", "Nc4", "b8", "Kd1d2", "Qd1"] {
+        assert_eq!(
+            board.parse_san(input),
+            Err(MoveParseError::Illegal(input.to_string()))
+        );
+    }
+
+    let board = Board::new("8/8/8/7k/8/Q7/8/Q1Q4K w - - 0 1");
+
+    assert_eq!(
+        board.parse_san("Qb2"),
+        Err(MoveParseError::Ambiguous("Qb2".to_string()))
+    );
+    assert_eq!(
+        board.parse_san("Qab2"),
+        Err(MoveParseError::Ambiguous("Qab2".to_string()))
+    );
+}
+
+#[test]
+fn status() {
+    let status = |fen: &str| Board::new(fen).status();
+    let draw = |termination| Status::Over(Outcome::draw(termination));
+
+    assert_eq!(status(STARTING_FEN), Status::Ongoing);
+    assert_eq!(
+        status("r1bqkb1r/pppp1Qpp/2n2n2/4p3/2B1P3/8/PPPP1PPP/RNB1K1NR b KQkq - 0 4"),
+        Status::Over(Outcome {
+            winner: Some(Color::White),
+            termination: Termination::Checkmate,
+        })
+    );
+    assert_eq!(
+        status("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1"),
+        draw(Termination::Stalemate)
+    );
+
+    // checkmate on the last move before the seventy-five-move rule still wins
+    assert_eq!(
+        status("7k/6Q1/6K1/8/8/8/8/8 b - - 150 100").result(),
+        crate::pgn::GameResult::WhiteWins
+    );
+    assert_eq!(
+        status("7k/8/6K1/8/8/8/8/R7 w - - 150 100"),
+        draw(Termination::SeventyFiveMoves)
+    );
+    assert_eq!(
+        status("7k/8/6K1/8/8/8/8/R7 w - - 100 75"),
+        Status::Claimable(Outcome::draw(Termination::FiftyMoves))
+    );
+    assert_eq!(status("7k/8/6K1/8/8/8/8/R7 w - - 99 75"), Status::Ongoing);
+}
+
+#[test]
+fn insufficient_material() {
+    let insufficient = |fen: &str| Board::new(fen).is_insufficient_material();
+
+    assert!(insufficient("7k/8/6K1/8/8/8/8/8 w - - 0 1"));
+    assert!(insufficient("7k/8/6K1/8/8/8/8/N7 w - - 0 1"));
+    assert!(insufficient("7k/8/6K1/8/8/8/8/b7 w - - 0 1"));
+    // bishops on squares of the same color, whatever side they belong to
+    assert!(insufficient("7k/8/6K1/8/8/8/1b6/B1B5 w - - 0 1"));
+    assert!(!insufficient("7k/8/6K1/8/8/8/8/Bb6 w - - 0 1"));
+    assert!(!insufficient("7k/8/6K1/8/8/8/8/Nn6 w - - 0 1"));
+    assert!(!insufficient("7k/8/6K1/8/8/8/8/NB6 w - - 0 1"));
+    assert!(!insufficient("7k/8/6K1/8/8/8/8/P7 w - - 0 1"));
+    assert!(!insufficient(STARTING_FEN));
+    assert_eq!(
+        Board::new("7k/8/6K1/8/8/8/8/N7 b - - 0 1").status(),
+        Status::Over(Outcome::draw(Termination::InsufficientMaterial))
+    );
+}
+
+#[test]
+fn checkers_and_attackers() {
+    // double check by the knight on f6 and the rook on e1
+    let board = Board::new("4k3/8/5N2/8/8/8/8/4R1K1 b - - 0 1");
+
+    assert!(board.is_in_check());
+    assert_eq!(board.checkers(), Bitboard::EMPTY | Square::F6 | Square::E1);
+    assert_eq!(
+        board.attackers_to(Square::E4, Color::White),
+        Bitboard::EMPTY | Square::F6 | Square::E1
+    );
+    assert!(board.is_square_attacked(Square::D7, Color::White));
+    assert!(!board.is_square_attacked(Square::D8, Color::White));
+    assert_eq!(
+        board.attackers_to(Square::D7, Color::Black),
+        Bitboard::from(Square::E8)
+    );
+
+    let board = Board::new(STARTING_FEN);
+    assert!(!board.is_in_check());
+    assert!(board.checkers().is_empty());
+    // pawns attack diagonally forward, the blocked sliders do not attack
+    assert_eq!(
+        board.attackers_to(Square::D6, Color::Black),
+        Bitboard::EMPTY | Square::C7 | Square::E7
+    );
+    assert!(!board.is_square_attacked(Square::D4, Color::White));
+}
+
+#[test]
+fn pinned_pieces() {
+    // the bishop on e2 is pinned by the rook on e8, the knight on c3 is shielded by the pawn on d2
+    // and the pawn on c1 is not alone between the king and the rook on a1
+    let board = Board::new("4r1k1/8/8/b7/8/2N5/3PB3/rNP1K3 w - - 0 1");
+    assert_eq!(
+        board.pinned_pieces(Color::White),
+        Bitboard::from(Square::E2)
+    );
+    assert!(board.pinned_pieces(Color::Black).is_empty());
+
+    let board = Board::new("4r1k1/8/8/b7/8/2N5/4B3/4K3 w - - 0 1");
+    assert_eq!(
+        board.pinned_pieces(Color::White),
+        Bitboard::EMPTY | Square::C3 | Square::E2
+    );
+
+    // only pieces of the king's own side are pinned
+    let board = Board::new("4k3/4n3/8/8/8/8/8/4RK2 b - - 0 1");
+    assert_eq!(
+        board.pinned_pieces(Color::Black),
+        Bitboard::from(Square::E7)
+    );
+    let board = Board::new("4k3/4N3/8/8/8/8/8/4RK2 b - - 0 1");
+    assert!(board.pinned_pieces(Color::Black).is_empty());
+}
+
+#[test]
+fn between_and_line() {
+    assert_eq!(
+        Board::between(Square::A1, Square::D4),
+        Bitboard::EMPTY | Square::B2 | Square::C3
+    );
+    assert_eq!(
+        Board::between(Square::E8, Square::E5),
+        Bitboard::EMPTY | Square::E7 | Square::E6
+    );
+    assert!(Board::between(Square::E1, Square::E2).is_empty());
+    assert!(Board::between(Square::A1, Square::B3).is_empty());
+
+    assert_eq!(Board::line(Square::B1, Square::E1), Bitboard(0xff));
+    assert_eq!(Board::line(Square::D4, Square::D5).count(), 8);
+    assert_eq!(
+        Board::line(Square::A7, Square::B8),
+        Bitboard::EMPTY | Square::A7 | Square::B8
+    );
+    assert_eq!(
+        Board::line(Square::F1, Square::D3),
+        [
+            Square::F1,
+            Square::E2,
+            Square::D3,
+            Square::C4,
+            Square::B5,
+            Square::A6
+        ]
+        .into_iter()
+        .collect()
+    );
+    assert!(Board::line(Square::A1, Square::A1).is_empty());
+    assert!(Board::line(Square::A1, Square::B3).is_empty());
+}
+
+#[test]
+fn see() {
+    let see = |fen: &str, san: &str| {
+        let board = Board::new(fen);
+        board.see(board.parse_san(san).unwrap())
+    };
+
+    assert_eq!(see("4k3/8/8/3p4/8/4N3/8/4K3 w - - 0 1", "Nxd5"), 100);
+    assert_eq!(see("4k3/8/4p3/3p4/8/4N3/8/4K3 w - - 0 1", "Nxd5"), -200);
+    assert_eq!(see("4k3/8/2p5/8/8/8/8/3RK3 w - - 0 1", "Rd5"), -500);
+    assert_eq!(see("4k3/8/8/8/8/8/8/3RK3 w - - 0 1", "Rd5"), 0);
+    assert_eq!(see(STARTING_FEN, "e4"), 0);
+    assert_eq!(see("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", "O-O"), 0);
+
+    // the rook behind the capturing rook joins the exchange
+    assert_eq!(see("3r2k1/8/8/3p4/8/8/3R4/3RK3 w - - 0 1", "Rxd5"), 100);
+    assert_eq!(see("3r2k1/8/8/3p4/8/8/3R4/4K3 w - - 0 1", "Rxd5"), -400);
+    // as does the queen behind the defending bishop
+    assert_eq!(see("4k3/8/8/3p4/2b5/1q2N3/8/4K3 w - - 0 1", "Nxd5"), -200);
+
+    // the king does not recapture a defended piece
+    assert_eq!(see("4k3/3p4/8/3Q4/8/8/8/3RK3 w - - 0 1", "Qxd7+"), 100);
+    assert_eq!(see("4k3/3p4/8/3Q4/8/8/8/4K3 w - - 0 1", "Qxd7+"), -800);
+
+    assert_eq!(see("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "exd6"), 100);
+    assert_eq!(see("4k3/2p5/8/3pP3/8/8/8/4K3 w - d6 0 1", "exd6"), 0);
+
+    // promotions gain the promoted piece, also when recapturing
+    assert_eq!(see("4k3/1P6/8/8/8/8/8/4K3 w - - 0 1", "b8=Q"), 800);
+    assert_eq!(see("r3k3/1Pn5/8/8/8/8/8/4K3 w - - 0 1", "bxa8=Q"), 400);
+    assert_eq!(see("r3k3/1P6/8/8/8/8/8/4K3 w - - 0 1", "bxa8=N"), 700);
+    assert_eq!(see("1Nr1k3/P7/8/8/8/8/8/4K3 b - - 0 1", "Rxb8"), -1000);
+}
+
+#[test]
+fn see_ge() {
+    let board = Board::new("4k3/8/4p3/3p4/8/4N3/8/4K3 w - - 0 1");
+    let m = board.parse_san("Nxd5").unwrap();
+
+    assert!(board.see_ge(m, -200));
+    assert!(!board.see_ge(m, -199));
+    assert!(!board.see_ge(m, 0));
+}
+
+#[test]
+fn generate_kinds() {
+    let board = Board::new("r3k3/1P6/8/8/8/8/8/R3K2R w KQq - 0 1");
+    let san = |kind| {
+        let mut moves: Vec<String> = board
+            .generate(kind)
+            .iter()
+            .map(|m| board.to_san(*m))
+            .collect();
+        moves.sort();
+        moves
+    };
+
+    // promotions to a queen are tactical moves, underpromotions only when capturing
+    assert_eq!(
+        san(MoveKind::Captures),
+        ["Rxa8+", "b8=Q+", "bxa8=B", "bxa8=N", "bxa8=Q+", "bxa8=R+"]
+    );
+    assert!(san(MoveKind::Quiets).contains(&"b8=N".to_string()));
+    assert!(san(MoveKind::Quiets).contains(&"O-O".to_string()));
+    assert_eq!(san(MoveKind::QuietChecks), ["Rh8+", "b8=R+"]);
+    assert!(san(MoveKind::Evasions).is_empty());
+
+    let board = Board::new("4k3/8/8/8/8/8/8/r3K3 w - - 0 1");
+    assert_eq!(board.generate(MoveKind::Evasions), board.legal_moves());
+    assert!(board.generate(MoveKind::QuietChecks).is_empty());
+}
+
+#[test]
+fn piece_lookup() {
+    let mut board = Board::new("4k3/8/8/3p4/4P3/8/8/R3K2R w KQ - 0 1");
+
+    assert_eq!(
+        board.piece_at(Square::E4),
+        Some((Color::White, PieceKind::Pawn))
+    );
+    assert_eq!(
+        board.piece_at(Square::E8),
+        Some((Color::Black, PieceKind::King))
+    );
+    assert_eq!(board.piece_at(Square::E5), None);
+    assert_eq!(
+        board.pieces(Color::White, PieceKind::Rook),
+        Bitboard::EMPTY | Square::A1 | Square::H1
+    );
+    assert!(board.pieces(Color::Black, PieceKind::Queen).is_empty());
+    assert_eq!(board.occupied().count(), 6);
+    assert_eq!(board.king_square(Color::Black), Some(Square::E8));
+    assert_eq!(
+        board.iter().collect::<Vec<(Square, Piece)>>(),
+        vec![
+            (Square::A1, Piece::WhiteRook),
+            (Square::E1, Piece::WhiteKing),
+            (Square::H1, Piece::WhiteRook),
+            (Square::E4, Piece::WhitePawn),
+            (Square::D5, Piece::BlackPawn),
+            (Square::E8, Piece::BlackKing),
+        ]
+    );
+
+    // the mailbox follows captures and castling, and is restored when undoing them
+    let before = board;
+    let capture = board.parse_san("exd5").unwrap();
+    let undo = board.make(capture);
+    assert_eq!(
+        board.piece_at(Square::D5),
+        Some((Color::White, PieceKind::Pawn))
+    );
+    assert_eq!(board.piece_at(Square::E4), None);
+    board.unmake(capture, undo);
+    assert_eq!(board, before);
+
+    let castled = board.make_move(&board.parse_san("O-O").unwrap()).unwrap();
+    assert_eq!(castled.king_square(Color::White), Some(Square::G1));
+    assert_eq!(
+        castled.piece_at(Square::F1),
+        Some((Color::White, PieceKind::Rook))
+    );
+    assert_eq!(castled.piece_at(Square::H1), None);
+
+    assert_eq!(
+        Board::new("8/8/8/8/8/8/8/8 w - - 0 1").king_square(Color::White),
+        None
+    );
+    for piece in Piece::ALL {
+        assert_eq!(Piece::new(piece.color(), piece.kind()), piece);
+    }
+}
+
+#[test]
+fn crowded_position() {
+    // an unreachable but valid position with more moves than any reachable position
+    let board = Board::from_fen("QQQQB2k/Q3QQ1N/Q5QQ/Q6Q/Q6Q/Q6Q/Q6Q/KQQQQQQQ w - - 0 1").unwrap();
+
+    assert!(board.validate().is_empty());
+    let possible = board.possible_white();
+    assert!(possible.len() > 256);
+    assert_eq!(
+        board.legal_moves().len(),
+        possible
+            .iter()
+            .filter(|m| board.make_move(m).is_ok())
+            .count()
+    );
+}
@@ -0,0 +1,305 @@
+use crate::board::{Board, Piece};
+use crate::color::Color;
+use crate::square::Square;
+use crate::validation::PositionError;
+
+///
+/// The castling rights of both sides.
+///
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Default)]
+pub struct CastlingRights {
+    /// Whether white can castle kingside.
+    pub white_kingside: bool,
+    /// Whether white can castle queenside.
+    pub white_queenside: bool,
+    /// Whether black can castle kingside.
+    pub black_kingside: bool,
+    /// Whether black can castle queenside.
+    pub black_queenside: bool,
+}
+
+impl CastlingRights {
+    ///
+    /// No side can castle.
+    ///
+    pub const NONE: CastlingRights = CastlingRights {
+        white_kingside: false,
+        white_queenside: false,
+        black_kingside: false,
+        black_queenside: false,
+    };
+
+    ///
+    /// Both sides can castle to both sides, as in the starting position.
+    ///
+    pub const ALL: CastlingRights = CastlingRights {
+        white_kingside: true,
+        white_queenside: true,
+        black_kingside: true,
+        black_queenside: true,
+    };
+}
+
+///
+/// A builder of boards, placing the pieces and setting the state of the position one by one.
+///
+/// # Examples
+///
+/// ```
+/// use osprey::{BoardBuilder, Color, Piece, Square};
+///
+/// let board = BoardBuilder::new()
+///     .put(Square::E1, Piece::WhiteKing)
+///     .put(Square::E8, Piece::BlackKing)
+///     .put(Square::D7, Piece::BlackPawn)
+///     .side_to_move(Color::Black)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(board.export_fen(), "4k3/3p4/8/8/8/8/8/4K3 b - - 0 1");
+/// ```
+///
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BoardBuilder {
+    /// The piece on every square, indexed by the index of the square.
+    pieces: [Option<Piece>; 64],
+    /// The side to move.
+    turn: Color,
+    /// The castling rights of both sides.
+    castling: CastlingRights,
+    /// The en passant square.
+    en_passant: Option<Square>,
+    /// The number of halfmoves since the last capture or pawn advance.
+    halfmove: u16,
+    /// The number of the full move.
+    fullmove: u16,
+}
+
+impl BoardBuilder {
+    ///
+    /// Creates a builder of an empty board with white to move, no castling rights, no en passant
+    /// square, a halfmove clock of 0 and a fullmove number of 1.
+    ///
+    pub fn new() -> BoardBuilder {
+        BoardBuilder {
+            pieces: [None; 64],
+            turn: Color::White,
+            castling: CastlingRights::NONE,
+            en_passant: None,
+            halfmove: 0,
+            fullmove: 1,
+        }
+    }
+
+    ///
+    /// Get the piece on a square.
+    ///
+    /// # Arguments
+    ///
+    /// * `square` - The square.
+    ///
+    pub fn piece_at(&self, square: Square) -> Option<Piece> {
+        self.pieces[square.index() as usize]
+    }
+
+    ///
+    /// Puts a piece on a square, replacing the piece on it.
+    ///
+    /// # Arguments
+    ///
+    /// * `square` - The square.
+    /// * `piece` - The piece.
+    ///
+    pub fn put(&mut self, square: Square, piece: Piece) -> &mut BoardBuilder {
+        self.pieces[square.index() as usize] = Some(piece);
+        self
+    }
+
+    ///
+    /// Removes the piece on a square, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `square` - The square.
+    ///
+    pub fn remove(&mut self, square: Square) -> &mut BoardBuilder {
+        self.pieces[square.index() as usize] = None;
+        self
+    }
+
+    ///
+    /// Removes all pieces, keeping the side to move, castling rights, en passant square and
+    /// counters.
+    ///
+    pub fn clear(&mut self) -> &mut BoardBuilder {
+        self.pieces = [None; 64];
+        self
+    }
+
+    ///
+    /// Sets the side to move.
+    ///
+    pub fn side_to_move(&mut self, color: Color) -> &mut BoardBuilder {
+        self.turn = color;
+        self
+    }
+
+    ///
+    /// Sets the castling rights of both sides.
+    ///
+    pub fn castling_rights(&mut self, rights: CastlingRights) -> &mut BoardBuilder {
+        self.castling = rights;
+        self
+    }
+
+    ///
+    /// Sets the en passant square, being the square a pawn passed over with a double push on the
+    /// last move.
+    ///
+    pub fn en_passant(&mut self, square: Option<Square>) -> &mut BoardBuilder {
+        self.en_passant = square;
+        self
+    }
+
+    ///
+    /// Sets the number of halfmoves since the last capture or pawn advance.
+    ///
+    pub fn halfmove(&mut self, halfmove: u16) -> &mut BoardBuilder {
+        self.halfmove = halfmove;
+        self
+    }
+
+    ///
+    /// Sets the number of the full move, where 0 is taken as 1.
+    ///
+    pub fn fullmove(&mut self, fullmove: u16) -> &mut BoardBuilder {
+        self.fullmove = fullmove.max(1);
+        self
+    }
+
+    ///
+    /// Builds the board.
+    ///
+    /// # Returns
+    ///
+    /// The board, or an error with the issues found by `Board::validate` if it is not a legal
+    /// chess position.
+    ///
+    pub fn build(&self) -> Result<Board, PositionError> {
+        let pieces = (0..64)
+            .map(Square::new)
+            .filter_map(|square| self.piece_at(square).map(|piece| (square, piece)));
+        let board = Board::from_parts(
+            pieces,
+            self.turn,
+            self.castling,
+            self.en_passant,
+            self.halfmove,
+            self.fullmove,
+        );
+        let issues = board.validate();
+
+        if issues.is_empty() {
+            Ok(board)
+        } else {
+            Err(PositionError::Illegal(issues))
+        }
+    }
+}
+
+impl Default for BoardBuilder {
+    fn default() -> BoardBuilder {
+        BoardBuilder::new()
+    }
+}
+
+impl From<&Board> for BoardBuilder {
+    ///
+    /// Creates a builder of a board, to edit the position.
+    ///
+    fn from(board: &Board) -> BoardBuilder {
+        let mut builder = BoardBuilder::new();
+
+        for (square, piece) in board {
+            builder.put(square, piece);
+        }
+
+        builder
+            .side_to_move(board.turn())
+            .castling_rights(board.castling_rights())
+            .en_passant(board.en_passant())
+            .halfmove(board.halfmove())
+            .fullmove(board.fullmove());
+
+        builder
+    }
+}
+
+#[test]
+fn build_and_edit() {
+    use crate::board::STARTING_FEN;
+    use crate::validation::PositionIssue;
+
+    let start = Board::new(STARTING_FEN);
+    let mut builder = BoardBuilder::from(&start);
+
+    assert_eq!(builder.build(), Ok(start));
+    assert_eq!(builder.piece_at(Square::D1), Some(Piece::WhiteQueen));
+
+    // 1. e4 built by hand has the same hash as 1. e4 played
+    builder
+        .remove(Square::E2)
+        .put(Square::E4, Piece::WhitePawn)
+        .side_to_move(Color::Black)
+        .en_passant(Some(Square::E3));
+    let played = start.make_move(&start.parse_san("e4").unwrap()).unwrap();
+    assert_eq!(builder.build(), Ok(played));
+    assert_eq!(builder.build().unwrap().hash(), played.hash());
+
+    // a replaced piece leaves no trace
+    builder.put(Square::E4, Piece::WhiteKnight).en_passant(None);
+    assert_eq!(
+        builder.build().unwrap().export_fen(),
+        "rnbqkbnr/pppppppp/8/8/4N3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1"
+    );
+
+    builder.clear().fullmove(0).halfmove(12);
+    assert_eq!(
+        builder.build(),
+        Err(PositionError::Illegal(vec![
+            PositionIssue::KingCount {
+                white: true,
+                count: 0
+            },
+            PositionIssue::KingCount {
+                white: false,
+                count: 0
+            },
+            PositionIssue::InvalidCastlingRights {
+                white: true,
+                kingside: true
+            },
+            PositionIssue::InvalidCastlingRights {
+                white: true,
+                kingside: false
+            },
+            PositionIssue::InvalidCastlingRights {
+                white: false,
+                kingside: true
+            },
+            PositionIssue::InvalidCastlingRights {
+                white: false,
+                kingside: false
+            },
+        ]))
+    );
+
+    let board = builder
+        .put(Square::A1, Piece::WhiteKing)
+        .put(Square::H8, Piece::BlackKing)
+        .castling_rights(CastlingRights::NONE)
+        .build()
+        .unwrap();
+    assert_eq!(board.export_fen(), "7k/8/8/8/8/8/8/K7 b - - 12 1");
+    assert_eq!(board.castling_rights(), CastlingRights::NONE);
+}
@@ -0,0 +1,107 @@
+use std::fmt;
+use std::ops::Not;
+
+///
+/// The color of a side.
+///
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Color {
+    White,
+    Black,
+}
+
+impl Color {
+    ///
+    /// Both colors, white first.
+    ///
+    pub const ALL: [Color; 2] = [Color::White, Color::Black];
+
+    ///
+    /// Get the color of a side.
+    ///
+    /// # Arguments
+    ///
+    /// * `white` - Whether the side is white.
+    ///
+    pub const fn from_white(white: bool) -> Color {
+        if white {
+            Color::White
+        } else {
+            Color::Black
+        }
+    }
+
+    ///
+    /// Whether the color is white.
+    ///
+    pub const fn is_white(self) -> bool {
+        matches!(self, Color::White)
+    }
+
+    ///
+    /// Get the letter of the color as used in FEN strings, `w` for white and `b` for black.
+    ///
+    pub const fn to_char(self) -> char {
+        match self {
+            Color::White => 'w',
+            Color::Black => 'b',
+        }
+    }
+
+    ///
+    /// Get the color from its letter as used in FEN strings.
+    ///
+    /// # Arguments
+    ///
+    /// * `c` - The letter, `w` for white and `b` for black.
+    ///
+    /// # Returns
+    ///
+    /// The color, or `None` if the letter is not a color.
+    ///
+    pub const fn from_char(c: char) -> Option<Color> {
+        match c {
+            'w' => Some(Color::White),
+            'b' => Some(Color::Black),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Get the color of the other side.
+    ///
+    pub const fn opposite(self) -> Color {
+        match self {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        }
+    }
+}
+
+impl Not for Color {
+    type Output = Color;
+
+    fn not(self) -> Color {
+        self.opposite()
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Color::White => write!(f, "white"),
+            Color::Black => write!(f, "black"),
+        }
+    }
+}
+
+#[test]
+fn color_opposite() {
+    assert_eq!(!Color::White, Color::Black);
+    assert_eq!(Color::Black.opposite(), Color::White);
+    assert_eq!(Color::from_white(false), Color::Black);
+    assert!(Color::White.is_white());
+    assert_eq!(Color::Black.to_string(), "black");
+    assert_eq!(Color::from_char(Color::White.to_char()), Some(Color::White));
+    assert_eq!(Color::from_char('x'), None);
+}
@@ -0,0 +1,154 @@
+use std::error::Error;
+use std::fmt;
+
+///
+/// A field of a FEN string.
+///
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum FenField {
+    /// The piece placement field.
+    Pieces,
+    /// The active color field.
+    Turn,
+    /// The castling availability field.
+    Castling,
+    /// The en passant target square field.
+    EnPassant,
+    /// The halfmove clock field.
+    HalfMove,
+    /// The fullmove number field.
+    FullMove,
+}
+
+impl FenField {
+    const ALL: [FenField; 6] = [
+        FenField::Pieces,
+        FenField::Turn,
+        FenField::Castling,
+        FenField::EnPassant,
+        FenField::HalfMove,
+        FenField::FullMove,
+    ];
+
+    ///
+    /// Get the field at the given index of a FEN string.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the field, starting at 0 for the piece placement.
+    ///
+    pub(crate) fn from_index(index: usize) -> Option<FenField> {
+        FenField::ALL.get(index).copied()
+    }
+}
+
+impl fmt::Display for FenField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            FenField::Pieces => "piece placement",
+            FenField::Turn => "active color",
+            FenField::Castling => "castling availability",
+            FenField::EnPassant => "en passant square",
+            FenField::HalfMove => "halfmove clock",
+            FenField::FullMove => "fullmove number",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+///
+/// The reason a FEN string could not be parsed.
+///
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum FenErrorKind {
+    /// The field is missing from the FEN string.
+    MissingField,
+    /// The FEN string contains more than six fields.
+    UnexpectedField,
+    /// The piece placement contains more than eight ranks.
+    TooManyRanks,
+    /// The piece placement contains less than eight ranks.
+    TooFewRanks,
+    /// The rank (0 for the first rank) describes more than eight squares.
+    TooManySquares { rank: u8 },
+    /// The rank (0 for the first rank) describes less than eight squares.
+    TooFewSquares { rank: u8 },
+    /// The character is not a valid piece letter or empty square count.
+    UnknownPiece(char),
+    /// The active color is neither `w` nor `b`.
+    InvalidTurn,
+    /// The character is not a valid castling letter.
+    InvalidCastling(char),
+    /// The castling letter appears more than once.
+    DuplicateCastling(char),
+    /// The en passant square is not `-` or a square on the third or sixth rank.
+    InvalidEnPassant,
+    /// The counter is not a number.
+    InvalidCounter,
+    /// The counter is a number, but is out of its allowed range.
+    CounterOutOfRange,
+}
+
+impl fmt::Display for FenErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FenErrorKind::MissingField => write!(f, "missing field"),
+            FenErrorKind::UnexpectedField => {
+                write!(f, "unexpected field after the fullmove number")
+            }
+            FenErrorKind::TooManyRanks => write!(f, "more than 8 ranks"),
+            FenErrorKind::TooFewRanks => write!(f, "less than 8 ranks"),
+            FenErrorKind::TooManySquares { rank } => {
+                write!(f, "more than 8 squares in rank {}", rank + 1)
+            }
+            FenErrorKind::TooFewSquares { rank } => {
+                write!(f, "less than 8 squares in rank {}", rank + 1)
+            }
+            FenErrorKind::UnknownPiece(c) => write!(f, "unknown piece '{}'", c),
+            FenErrorKind::InvalidTurn => write!(f, "expected 'w' or 'b'"),
+            FenErrorKind::InvalidCastling(c) => write!(f, "invalid castling letter '{}'", c),
+            FenErrorKind::DuplicateCastling(c) => write!(f, "duplicate castling letter '{}'", c),
+            FenErrorKind::InvalidEnPassant => {
+                write!(f, "expected '-' or a square on the 3rd or 6th rank")
+            }
+            FenErrorKind::InvalidCounter => write!(f, "expected a number"),
+            FenErrorKind::CounterOutOfRange => write!(f, "number out of range"),
+        }
+    }
+}
+
+///
+/// An error returned when parsing a malformed FEN string.
+///
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FenError {
+    /// The field in which the error occurred.
+    pub field: FenField,
+    /// The character offset in the FEN string at which the error occurred.
+    pub offset: usize,
+    /// The reason of the error.
+    pub kind: FenErrorKind,
+}
+
+impl FenError {
+    pub(crate) fn new(field: FenField, offset: usize, kind: FenErrorKind) -> FenError {
+        FenError {
+            field,
+            offset,
+            kind,
+        }
+    }
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid {} at offset {}: {}",
+            self.field, self.offset, self.kind
+        )
+    }
+}
+
+impl Error for FenError {}
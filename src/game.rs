@@ -0,0 +1,344 @@
+#[cfg(test)]
+use crate::board::STARTING_FEN;
+use crate::board::{Board, Move, UndoInfo};
+use crate::notation::MoveParseError;
+use crate::outcome::{Outcome, Status, Termination};
+use crate::pgn::PgnGame;
+
+///
+/// A game of chess, being a start position and the moves made from it.
+///
+/// The game keeps the position after every move, so moves can be undone and the game can jump
+/// back and forth between its plies. Making a move at an earlier ply replaces the moves after it.
+///
+#[derive(Debug, Clone)]
+pub struct Game {
+    /// The position the game starts from.
+    start: Board,
+    /// The current position.
+    board: Board,
+    /// All moves of the game, including those after the current ply.
+    moves: Vec<Move>,
+    /// The information needed to undo the moves up to the current ply.
+    undo: Vec<UndoInfo>,
+    /// The Zobrist keys of the positions up to the current ply, starting with the start position.
+    hashes: Vec<u64>,
+}
+
+impl Game {
+    ///
+    /// Creates a game without moves.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The position the game starts from.
+    ///
+    pub fn new(start: Board) -> Game {
+        Game {
+            start,
+            board: start,
+            moves: Vec::new(),
+            undo: Vec::new(),
+            hashes: vec![start.hash()],
+        }
+    }
+
+    ///
+    /// Get the position the game starts from.
+    ///
+    pub fn start(&self) -> &Board {
+        &self.start
+    }
+
+    ///
+    /// Get the position at the current ply.
+    ///
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    ///
+    /// Get all moves of the game, including those after the current ply.
+    ///
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    ///
+    /// Get the current ply, being the number of moves made from the start position.
+    ///
+    pub fn ply(&self) -> usize {
+        self.undo.len()
+    }
+
+    ///
+    /// Makes a move at the current ply, replacing the moves after it.
+    ///
+    /// # Arguments
+    ///
+    /// * `m` - The move, which should be legal in the current position.
+    ///
+    /// # Returns
+    ///
+    /// An error if the move is not legal.
+    ///
+    pub fn push(&mut self, m: Move) -> Result<(), MoveParseError> {
+        if !self.board.legal_moves().contains(&m) {
+            return Err(MoveParseError::Illegal(m.to_uci()));
+        }
+
+        self.moves.truncate(self.ply());
+        self.moves.push(m);
+        self.forward();
+
+        Ok(())
+    }
+
+    ///
+    /// Undoes the move before the current ply, removing it and the moves after it.
+    ///
+    /// # Returns
+    ///
+    /// The undone move, or `None` at the start of the game.
+    ///
+    pub fn pop(&mut self) -> Option<Move> {
+        self.back()?;
+        self.moves.truncate(self.ply() + 1);
+        self.moves.pop()
+    }
+
+    ///
+    /// Jumps to a ply, keeping all moves of the game.
+    ///
+    /// # Arguments
+    ///
+    /// * `ply` - The ply, between 0 for the start position and the number of moves (both
+    ///   included).
+    ///
+    /// # Returns
+    ///
+    /// Whether the game has the ply. The game stays at the current ply when it does not.
+    ///
+    pub fn go_to(&mut self, ply: usize) -> bool {
+        if ply > self.moves.len() {
+            return false;
+        }
+
+        while self.ply() > ply {
+            self.back();
+        }
+        while self.ply() < ply {
+            self.forward();
+        }
+
+        true
+    }
+
+    ///
+    /// Get the number of times the current position occurred up to the current ply.
+    ///
+    /// Positions are the same when the same pieces are on the same squares with the same side to
+    /// move, castling rights and en passant capture, as given by their Zobrist keys. Only the
+    /// positions since the last capture or pawn advance can repeat the current position.
+    ///
+    pub fn repetitions(&self) -> usize {
+        let hash = self.board.hash();
+        let reversible = (self.board.halfmove() as usize).min(self.hashes.len() - 1);
+
+        self.hashes
+            .iter()
+            .rev()
+            .take(reversible + 1)
+            .step_by(2)
+            .filter(|h| **h == hash)
+            .count()
+    }
+
+    ///
+    /// Whether the current position occurred at least three times, allowing a player to claim a
+    /// draw.
+    ///
+    pub fn is_threefold_repetition(&self) -> bool {
+        self.repetitions() >= 3
+    }
+
+    ///
+    /// Whether the current position occurred at least five times, which draws the game.
+    ///
+    pub fn is_fivefold_repetition(&self) -> bool {
+        self.repetitions() >= 5
+    }
+
+    ///
+    /// Get the status of the game at the current ply.
+    ///
+    /// # Returns
+    ///
+    /// The status of the current position as given by `Board::status`, where a fivefold
+    /// repetition also ends the game and a threefold repetition allows a player to claim a draw.
+    ///
+    pub fn status(&self) -> Status {
+        let status = self.board.status();
+
+        if status.is_over() {
+            status
+        } else if self.is_fivefold_repetition() {
+            Status::Over(Outcome::draw(Termination::FivefoldRepetition))
+        } else if self.is_threefold_repetition() {
+            Status::Claimable(Outcome::draw(Termination::ThreefoldRepetition))
+        } else {
+            status
+        }
+    }
+
+    ///
+    /// Makes the move after the current ply.
+    ///
+    /// # Returns
+    ///
+    /// The made move, or `None` at the last ply.
+    ///
+    fn forward(&mut self) -> Option<Move> {
+        let m = *self.moves.get(self.ply())?;
+
+        self.undo.push(self.board.make(m));
+        self.hashes.push(self.board.hash());
+
+        Some(m)
+    }
+
+    ///
+    /// Undoes the move before the current ply, keeping it in the moves of the game.
+    ///
+    /// # Returns
+    ///
+    /// The undone move, or `None` at the start of the game.
+    ///
+    fn back(&mut self) -> Option<Move> {
+        let undo = self.undo.pop()?;
+        let m = self.moves[self.undo.len()];
+
+        self.board.unmake(m, undo);
+        self.hashes.pop();
+
+        Some(m)
+    }
+}
+
+impl From<&PgnGame> for Game {
+    ///
+    /// Creates a game from the main line of a PGN game, at its last ply.
+    ///
+    fn from(pgn: &PgnGame) -> Game {
+        let mut game = Game::new(pgn.start);
+
+        game.moves = pgn.moves.iter().map(|m| m.m).collect();
+        game.go_to(game.moves.len());
+
+        game
+    }
+}
+
+#[test]
+fn push_pop_and_go_to() {
+    let mut game = Game::new(Board::new(STARTING_FEN));
+    let start = *game.board();
+
+    for san in ["e4", "e5", "Nf3"] {
+        let m = game.board().parse_san(san).unwrap();
+        game.push(m).unwrap();
+    }
+    let after = *game.board();
+
+    assert_eq!(game.ply(), 3);
+    assert!(game.go_to(1));
+    assert_eq!(
+        game.board().export_fen(),
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+    );
+    assert_eq!(game.moves().len(), 3);
+    assert!(!game.go_to(4));
+    assert!(game.go_to(3));
+    assert_eq!(*game.board(), after);
+
+    // a move at an earlier ply replaces the later moves
+    game.go_to(2);
+    let m = game.board().parse_san("Nc3").unwrap();
+    game.push(m).unwrap();
+    assert_eq!(game.moves().len(), 3);
+    assert_eq!(game.pop(), Some(m));
+    assert_eq!(game.moves().len(), 2);
+
+    game.go_to(0);
+    assert_eq!(*game.board(), start);
+    assert_eq!(game.pop(), None);
+
+    let illegal = Board::new("8/8/8/8/8/8/8/K6k w - - 0 1")
+        .parse_san("Kb2")
+        .unwrap();
+    assert_eq!(
+        game.push(illegal),
+        Err(MoveParseError::Illegal("a1b2".to_string()))
+    );
+}
+
+#[test]
+fn repetitions() {
+    let mut game = Game::new(Board::new(STARTING_FEN));
+    assert_eq!(game.repetitions(), 1);
+
+    for i in 0..16 {
+        let san = ["Nf3", "Nf6", "Ng1", "Ng8"][i % 4];
+        let m = game.board().parse_san(san).unwrap();
+        game.push(m).unwrap();
+
+        // the start position occurs again after every fourth ply
+        assert_eq!(
+            game.repetitions(),
+            if i % 4 == 3 { i / 4 + 2 } else { i / 4 + 1 }
+        );
+        assert_eq!(
+            game.is_threefold_repetition(),
+            (i >= 7 && i % 4 == 3) || i >= 8
+        );
+    }
+
+    assert!(game.is_fivefold_repetition());
+
+    // a pawn advance makes the earlier positions unreachable
+    game.push(game.board().parse_san("e4").unwrap()).unwrap();
+    assert_eq!(game.repetitions(), 1);
+
+    game.pop();
+    assert!(game.is_fivefold_repetition());
+}
+
+#[test]
+fn status() {
+    let mut game = Game::new(Board::new(STARTING_FEN));
+
+    for (i, san) in ["Nf3", "Nf6", "Ng1", "Ng8"]
+        .iter()
+        .cycle()
+        .take(16)
+        .enumerate()
+    {
+        let m = game.board().parse_san(san).unwrap();
+        game.push(m).unwrap();
+
+        let expected = match i {
+            15 => Status::Over(Outcome::draw(Termination::FivefoldRepetition)),
+            7.. => Status::Claimable(Outcome::draw(Termination::ThreefoldRepetition)),
+            _ => Status::Ongoing,
+        };
+        assert_eq!(game.status(), expected, "ply {}", i + 1);
+    }
+
+    // the status of the position comes before repetitions
+    let mut game = Game::new(Board::new(STARTING_FEN));
+    for san in ["f3", "e5", "g4", "Qh4#"] {
+        let m = game.board().parse_san(san).unwrap();
+        game.push(m).unwrap();
+    }
+    assert_eq!(game.status().result(), crate::pgn::GameResult::BlackWins);
+}
@@ -1,9 +1,37 @@
 // Make module public
-pub mod utils; 
+pub mod bitboard;
 pub mod board;
+pub mod builder;
+pub mod color;
+pub mod fen;
+pub mod game;
+pub mod magic;
+pub mod movelist;
+pub mod notation;
+pub mod outcome;
+pub mod packedmove;
 pub mod perft;
+pub mod pgn;
+pub mod polyglot;
+#[cfg(feature = "serde")]
+mod serialization;
+pub mod square;
+pub mod validation;
+pub mod zobrist;
 
 // Make the function available at the root of the crate
-pub use utils::*;
+pub use bitboard::*;
 pub use board::*;
-pub use perft::*;
\ No newline at end of file
+pub use builder::*;
+pub use color::*;
+pub use fen::*;
+pub use game::*;
+pub use movelist::*;
+pub use notation::*;
+pub use outcome::*;
+pub use packedmove::*;
+pub use perft::*;
+pub use pgn::*;
+pub use polyglot::*;
+pub use square::*;
+pub use validation::*;
@@ -0,0 +1,320 @@
+use std::sync::OnceLock;
+
+///
+/// The magic numbers of the rook attacks per square.
+///
+#[rustfmt::skip]
+const ROOK_MAGICS: [u64; 64] = [
+    0x0a80004000801220, 0x10c0100040002000, 0x0100102000410009,
+    0x0b0021000c100008, 0x4080080080040002, 0x0200019004080200,
+    0x0400080a10112684, 0x20800a4d00062080, 0x2091800020804000,
+    0x0044401000200040, 0x1001002000401108, 0x1001800801100081,
+    0x0001000500080010, 0x1000808002000400, 0x0404000482100108,
+    0x0003000182610002, 0x0440848002c00420, 0x2010890040010021,
+    0x8800110020044300, 0x0208010100201000, 0x1222020004102008,
+    0x0000808002000400, 0x20040400094a9008, 0x0000420000804401,
+    0x0040002880004680, 0x0000200240100040, 0x0020008180201001,
+    0x01080080800c1000, 0x0104040080800800, 0x4800020080040080,
+    0x0002000200840108, 0x00a1000100006082, 0x8004400088800260,
+    0x0100804000802008, 0x0010008010802002, 0x000c801000800800,
+    0x0c51800402800800, 0x0002800200800400, 0x0000820804000110,
+    0x4003808042000401, 0x00208020c0018000, 0x4400402010004009,
+    0x22100400a800e000, 0x0e020021400a0013, 0x10a0080100110005,
+    0x0004010002004040, 0x0024080102040010, 0x4154089108420014,
+    0x0182400080002380, 0x0000400110802100, 0x0000100080200480,
+    0x100a000820401200, 0x8081004020801002, 0x0002000408100200,
+    0x03223a1008010c00, 0x000000831c014200, 0x4200208009001041,
+    0xc001004000881021, 0x1008200100100841, 0x0000082240920032,
+    0x4002000804201102, 0xb821000804000201, 0x4080c208102100a4,
+    0x02020900418c0ca2,
+];
+
+///
+/// The magic numbers of the bishop attacks per square.
+///
+#[rustfmt::skip]
+const BISHOP_MAGICS: [u64; 64] = [
+    0x002a840401840308, 0x0002048404004000, 0x1088508106020000,
+    0x0604040484000420, 0x1002021004380001, 0x8041048240000a30,
+    0x4084044104103110, 0x0081004044200840, 0x04424110a1010901,
+    0x0042820841040080, 0x1001080204002c09, 0x00014804a1041815,
+    0x0004820210041001, 0x0001810403400040, 0x080240410420a084,
+    0x0410120201010900, 0x0240048504280200, 0x9402000490040325,
+    0x200300101c098030, 0x0004002840400800, 0x0002010402110140,
+    0x000040320100a060, 0x0042000061046000, 0x0188300084684808,
+    0x0010101108021022, 0x8724048021090c00, 0x502c0202c4080010,
+    0x0008082248020020, 0x0001020084008400, 0x0891004002082001,
+    0x000a021004248200, 0x00110200012a8402, 0x2042208430203904,
+    0x0c08620816111880, 0x0002045004410100, 0x0800120180180080,
+    0x0140010012c10040, 0x0050100840402400, 0x0808020080004801,
+    0x0004820341020100, 0x001a01242010c080, 0x2018620210012008,
+    0x80210400220a0400, 0x0020014200820801, 0x010008810041c400,
+    0x0020040880205a01, 0x001081011102e420, 0x00810604810b0208,
+    0x0000621004210094, 0x0200210802105811, 0x8008008058080200,
+    0x5800400084040010, 0x0000009002022880, 0x9000883001021010,
+    0x804a828404040006, 0x2010121801002800, 0x4012020084010846,
+    0x8002421202020200, 0x6104040020841000, 0x0000000205048804,
+    0x0808008041102480, 0x2305904002040440, 0x0810404282020204,
+    0x0588200102002100,
+];
+
+const ROOK_DIRECTIONS: [(i8, i8); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+const BISHOP_DIRECTIONS: [(i8, i8); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+///
+/// The magic lookup of a slider on a square.
+///
+#[derive(Debug, Clone, Copy)]
+struct Magic {
+    /// A bitboard representing the squares whose occupancy can block the slider.
+    mask: u64,
+    /// The magic number mapping the blocking occupancy to an index, unused with PEXT.
+    #[cfg_attr(
+        all(feature = "pext", target_arch = "x86_64", target_feature = "bmi2"),
+        allow(dead_code)
+    )]
+    magic: u64,
+    /// The shift applied to the product of the occupancy and the magic number, unused with PEXT.
+    #[cfg_attr(
+        all(feature = "pext", target_arch = "x86_64", target_feature = "bmi2"),
+        allow(dead_code)
+    )]
+    shift: u32,
+    /// The offset of the attacks of this square in the attack table.
+    offset: usize,
+}
+
+impl Magic {
+    ///
+    /// Calculates the index of the attacks for the given occupancy in the attack table.
+    ///
+    /// With the `pext` feature on x86_64 processors supporting BMI2 the index is calculated with
+    /// the PEXT instruction instead of a magic multiplication.
+    ///
+    #[inline]
+    fn index(&self, occupied: u64) -> usize {
+        #[cfg(all(feature = "pext", target_arch = "x86_64", target_feature = "bmi2"))]
+        {
+            // SAFETY: this is only compiled when the target supports BMI2
+            self.offset + unsafe { std::arch::x86_64::_pext_u64(occupied, self.mask) } as usize
+        }
+
+        #[cfg(not(all(feature = "pext", target_arch = "x86_64", target_feature = "bmi2")))]
+        {
+            self.offset + ((occupied & self.mask).wrapping_mul(self.magic) >> self.shift) as usize
+        }
+    }
+}
+
+///
+/// The precomputed attack tables of the sliders.
+///
+struct SliderTables {
+    /// The rook lookups per square.
+    rook: [Magic; 64],
+    /// The bishop lookups per square.
+    bishop: [Magic; 64],
+    /// The attacks of all squares and blocking occupancies.
+    attacks: Vec<u64>,
+}
+
+static TABLES: OnceLock<SliderTables> = OnceLock::new();
+
+///
+/// Calculates the squares attacked by a slider by walking its rays.
+///
+/// # Arguments
+///
+/// * `position` - The position of the slider as a number between 0 and 63 (both included).
+/// * `occupied` - A bitboard representing all occupied squares.
+/// * `directions` - The (rank, file) directions the slider moves in.
+///
+fn sliding_attacks(position: u8, occupied: u64, directions: &[(i8, i8); 4]) -> u64 {
+    let mut attacks = 0;
+
+    for (rank_step, file_step) in directions {
+        let mut rank = (position / 8) as i8 + rank_step;
+        let mut file = (position % 8) as i8 + file_step;
+
+        while (0..8).contains(&rank) && (0..8).contains(&file) {
+            let square = 1u64 << (rank * 8 + file);
+            attacks |= square;
+
+            if occupied & square != 0 {
+                break;
+            }
+
+            rank += rank_step;
+            file += file_step;
+        }
+    }
+
+    attacks
+}
+
+///
+/// Calculates the squares whose occupancy can block a slider, which are all squares it attacks
+/// on an empty board except the last square of each ray.
+///
+fn blocker_mask(position: u8, directions: &[(i8, i8); 4]) -> u64 {
+    let mut mask = 0;
+
+    for (rank_step, file_step) in directions {
+        let mut rank = (position / 8) as i8 + rank_step;
+        let mut file = (position % 8) as i8 + file_step;
+
+        while (0..8).contains(&(rank + rank_step)) && (0..8).contains(&(file + file_step)) {
+            mask |= 1u64 << (rank * 8 + file);
+
+            rank += rank_step;
+            file += file_step;
+        }
+    }
+
+    mask
+}
+
+///
+/// Builds the lookups of a slider and fills its part of the attack table.
+///
+fn build(magics: &[u64; 64], directions: &[(i8, i8); 4], attacks: &mut Vec<u64>) -> [Magic; 64] {
+    let mut lookups = [Magic {
+        mask: 0,
+        magic: 0,
+        shift: 0,
+        offset: 0,
+    }; 64];
+
+    for position in 0..64u8 {
+        let mask = blocker_mask(position, directions);
+        let bits = mask.count_ones();
+
+        let magic = Magic {
+            mask,
+            magic: magics[position as usize],
+            shift: 64 - bits,
+            offset: attacks.len(),
+        };
+
+        attacks.resize(attacks.len() + (1 << bits), 0);
+
+        // enumerate all subsets of the mask
+        let mut occupied = 0u64;
+        loop {
+            attacks[magic.index(occupied)] = sliding_attacks(position, occupied, directions);
+
+            occupied = occupied.wrapping_sub(mask) & mask;
+            if occupied == 0 {
+                break;
+            }
+        }
+
+        lookups[position as usize] = magic;
+    }
+
+    lookups
+}
+
+///
+/// Get the slider tables, computing them on the first call.
+///
+fn tables() -> &'static SliderTables {
+    TABLES.get_or_init(|| {
+        let mut attacks: Vec<u64> = Vec::new();
+
+        let rook = build(&ROOK_MAGICS, &ROOK_DIRECTIONS, &mut attacks);
+        let bishop = build(&BISHOP_MAGICS, &BISHOP_DIRECTIONS, &mut attacks);
+
+        SliderTables {
+            rook,
+            bishop,
+            attacks,
+        }
+    })
+}
+
+///
+/// Computes the slider attack tables.
+///
+/// The tables are computed on first use, so calling this at startup only moves that cost out of
+/// the first move generation.
+///
+pub fn init() {
+    tables();
+}
+
+///
+/// Get the squares attacked by a rook.
+///
+/// # Arguments
+///
+/// * `position` - The position of the rook as a number between 0 and 63 (both included).
+/// * `occupied` - A bitboard representing all occupied squares.
+///
+/// # Returns
+///
+/// A bitboard representing the attacked squares, including the first blocker of each ray.
+///
+#[inline]
+pub(crate) fn rook_attacks(position: u8, occupied: u64) -> u64 {
+    let tables = tables();
+    tables.attacks[tables.rook[position as usize].index(occupied)]
+}
+
+///
+/// Get the squares attacked by a bishop.
+///
+/// # Arguments
+///
+/// * `position` - The position of the bishop as a number between 0 and 63 (both included).
+/// * `occupied` - A bitboard representing all occupied squares.
+///
+/// # Returns
+///
+/// A bitboard representing the attacked squares, including the first blocker of each ray.
+///
+#[inline]
+pub(crate) fn bishop_attacks(position: u8, occupied: u64) -> u64 {
+    let tables = tables();
+    tables.attacks[tables.bishop[position as usize].index(occupied)]
+}
+
+#[test]
+fn magic_attacks_match_rays() {
+    // pseudo-random occupancies
+    let mut occupied: u64 = 0x9e37_79b9_7f4a_7c15;
+
+    for _ in 0..64 {
+        occupied ^= occupied << 13;
+        occupied ^= occupied >> 7;
+        occupied ^= occupied << 17;
+
+        let sparse = occupied & (occupied >> 3);
+
+        for position in 0..64 {
+            assert_eq!(
+                rook_attacks(position, sparse),
+                sliding_attacks(position, sparse, &ROOK_DIRECTIONS)
+            );
+            assert_eq!(
+                bishop_attacks(position, sparse),
+                sliding_attacks(position, sparse, &BISHOP_DIRECTIONS)
+            );
+        }
+    }
+}
+
+#[test]
+fn rook_attacks_blocked() {
+    // rook on d4, blockers on d6, b4 and d1
+    let occupied = (1u64 << 43) | (1u64 << 25) | (1u64 << 3);
+    let attacks = 0x0000_0808_f608_0808;
+
+    assert_eq!(rook_attacks(27, occupied), attacks);
+}
+
+#[test]
+fn bishop_attacks_corner() {
+    assert_eq!(bishop_attacks(0, 0), 0x8040_2010_0804_0200);
+    assert_eq!(bishop_attacks(0, 1u64 << 27), 0x0804_0200);
+}
@@ -1,13 +1,11 @@
 #![crate_name = "osprey"]
 
-use crate::board::Board;
-use crate::perft::perft;
 use clap::{Parser, Subcommand};
+use osprey::board::Board;
+use osprey::magic;
+use osprey::perft::perft;
 use std::thread::available_parallelism;
 
-mod board;
-mod perft;
-
 #[derive(Parser)]
 struct App {
     #[clap(subcommand)]
@@ -35,6 +33,9 @@ enum Command {
 fn main() {
     let args = App::parse();
 
+    // Compute the slider attack tables before any timing starts
+    magic::init();
+
     match args.command {
         Command::Perft {
             load_file,
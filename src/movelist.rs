@@ -0,0 +1,297 @@
+use crate::board::Move;
+use crate::square::Square;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+///
+/// The maximum number of moves a `MoveList` can hold.
+///
+/// This bounds the pseudo-legal moves of any position a FEN string can describe, reachable or
+/// not. A side with `n` pieces moves each of them to at most `min(27, 64 - n)` squares not holding
+/// its own pieces, and a pawn has at most 12 moves counting every promotion, so the side has at
+/// most `n * max(min(27, 64 - n), 12) <= 37 * 27 = 999` moves.
+///
+pub const MAX_MOVES: usize = 1024;
+
+///
+/// A list of moves with a fixed capacity, stored on the stack.
+///
+/// The list dereferences to a slice of its moves, so it supports indexing, iterating and sorting
+/// like a vector.
+///
+#[derive(Clone, Copy)]
+pub struct MoveList {
+    /// The moves, of which only the first `len` are valid.
+    moves: [Move; MAX_MOVES],
+    /// The number of moves in the list.
+    len: usize,
+}
+
+impl MoveList {
+    ///
+    /// Creates an empty move list.
+    ///
+    pub fn new() -> MoveList {
+        MoveList {
+            moves: [Move::Normal {
+                from: Square::A1,
+                to: Square::A1,
+            }; MAX_MOVES],
+            len: 0,
+        }
+    }
+
+    ///
+    /// Adds a move to the end of the list.
+    ///
+    /// # Arguments
+    ///
+    /// * `m` - The move to add.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the list already holds `MAX_MOVES` moves.
+    ///
+    #[inline]
+    pub fn push(&mut self, m: Move) {
+        self.moves[self.len] = m;
+        self.len += 1;
+    }
+
+    ///
+    /// Removes the last move of the list.
+    ///
+    /// # Returns
+    ///
+    /// The removed move, or `None` if the list is empty.
+    ///
+    pub fn pop(&mut self) -> Option<Move> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        Some(self.moves[self.len])
+    }
+
+    ///
+    /// Removes all moves from the list.
+    ///
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    ///
+    /// Keeps only the moves for which the predicate returns true, preserving their order.
+    ///
+    /// # Arguments
+    ///
+    /// * `keep` - The predicate deciding whether a move is kept.
+    ///
+    pub fn retain<F: FnMut(&Move) -> bool>(&mut self, mut keep: F) {
+        let mut kept = 0;
+
+        for i in 0..self.len {
+            if keep(&self.moves[i]) {
+                self.moves[kept] = self.moves[i];
+                kept += 1;
+            }
+        }
+
+        self.len = kept;
+    }
+
+    ///
+    /// Get the moves as a slice.
+    ///
+    pub fn as_slice(&self) -> &[Move] {
+        &self.moves[..self.len]
+    }
+
+    ///
+    /// Get the moves as a mutable slice.
+    ///
+    pub fn as_mut_slice(&mut self) -> &mut [Move] {
+        &mut self.moves[..self.len]
+    }
+}
+
+impl Default for MoveList {
+    fn default() -> MoveList {
+        MoveList::new()
+    }
+}
+
+impl Deref for MoveList {
+    type Target = [Move];
+
+    fn deref(&self) -> &[Move] {
+        self.as_slice()
+    }
+}
+
+impl DerefMut for MoveList {
+    fn deref_mut(&mut self) -> &mut [Move] {
+        self.as_mut_slice()
+    }
+}
+
+impl PartialEq for MoveList {
+    fn eq(&self, other: &MoveList) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for MoveList {}
+
+impl fmt::Debug for MoveList {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Extend<Move> for MoveList {
+    fn extend<I: IntoIterator<Item = Move>>(&mut self, moves: I) {
+        for m in moves {
+            self.push(m);
+        }
+    }
+}
+
+impl FromIterator<Move> for MoveList {
+    fn from_iter<I: IntoIterator<Item = Move>>(moves: I) -> MoveList {
+        let mut list = MoveList::new();
+        list.extend(moves);
+        list
+    }
+}
+
+///
+/// An iterator over the moves of a `MoveList`, taking ownership of the list.
+///
+pub struct IntoIter {
+    /// The iterated list.
+    list: MoveList,
+    /// The index of the next move.
+    index: usize,
+}
+
+impl Iterator for IntoIter {
+    type Item = Move;
+
+    fn next(&mut self) -> Option<Move> {
+        if self.index == self.list.len {
+            return None;
+        }
+
+        self.index += 1;
+        Some(self.list.moves[self.index - 1])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.list.len - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for IntoIter {}
+
+impl IntoIterator for MoveList {
+    type Item = Move;
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> IntoIter {
+        IntoIter {
+            list: self,
+            index: 0,
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a Move;
+    type IntoIter = std::slice::Iter<'a, Move>;
+
+    fn into_iter(self) -> std::slice::Iter<'a, Move> {
+        self.as_slice().iter()
+    }
+}
+
+#[test]
+fn push_and_index() {
+    let mut moves = MoveList::new();
+    assert!(moves.is_empty());
+
+    moves.push(Move::Normal {
+        from: Square::E2,
+        to: Square::E4,
+    });
+    moves.push(Move::Normal {
+        from: Square::G1,
+        to: Square::F3,
+    });
+
+    assert_eq!(moves.len(), 2);
+    assert_eq!(
+        moves[1],
+        Move::Normal {
+            from: Square::G1,
+            to: Square::F3
+        }
+    );
+    assert_eq!(
+        moves.pop(),
+        Some(Move::Normal {
+            from: Square::G1,
+            to: Square::F3
+        })
+    );
+    assert_eq!(moves.len(), 1);
+}
+
+#[test]
+fn sort_and_retain() {
+    let mut moves: MoveList = [Square::G4, Square::C2, Square::E3]
+        .iter()
+        .map(|to| Move::Normal {
+            from: Square::A1,
+            to: *to,
+        })
+        .collect();
+
+    moves.sort_by_key(|m| m.to_square());
+    moves.retain(|m| {
+        *m != Move::Normal {
+            from: Square::A1,
+            to: Square::E3,
+        }
+    });
+
+    let targets: Vec<Move> = moves.into_iter().collect();
+    assert_eq!(
+        targets,
+        vec![
+            Move::Normal {
+                from: Square::A1,
+                to: Square::C2
+            },
+            Move::Normal {
+                from: Square::A1,
+                to: Square::G4
+            }
+        ]
+    );
+}
+
+#[test]
+#[should_panic]
+fn push_beyond_capacity() {
+    let mut moves = MoveList::new();
+
+    for _ in 0..=MAX_MOVES {
+        moves.push(Move::Normal {
+            from: Square::A1,
+            to: Square::B1,
+        });
+    }
+}
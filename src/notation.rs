@@ -0,0 +1,27 @@
+use std::error::Error;
+use std::fmt;
+
+///
+/// An error returned when parsing a move written in a move notation.
+///
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MoveParseError {
+    /// The string is not a move in the notation.
+    Malformed(String),
+    /// The string is a move in the notation, but the move is not legal in the position.
+    Illegal(String),
+    /// The string matches more than one legal move in the position.
+    Ambiguous(String),
+}
+
+impl fmt::Display for MoveParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MoveParseError::Malformed(input) => write!(f, "malformed move '{}'", input),
+            MoveParseError::Illegal(input) => write!(f, "illegal move '{}'", input),
+            MoveParseError::Ambiguous(input) => write!(f, "ambiguous move '{}'", input),
+        }
+    }
+}
+
+impl Error for MoveParseError {}
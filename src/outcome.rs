@@ -0,0 +1,158 @@
+use crate::color::Color;
+use crate::pgn::GameResult;
+use std::fmt;
+
+///
+/// The reason a game ended or a draw can be claimed.
+///
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Termination {
+    /// The side to move is in check and has no legal moves.
+    Checkmate,
+    /// The side to move is not in check and has no legal moves.
+    Stalemate,
+    /// Neither side has the pieces left to checkmate the other.
+    InsufficientMaterial,
+    /// No capture or pawn advance was made in the last fifty moves of each side, allowing a player
+    /// to claim a draw.
+    FiftyMoves,
+    /// No capture or pawn advance was made in the last seventy-five moves of each side.
+    SeventyFiveMoves,
+    /// The position occurred three times, allowing a player to claim a draw.
+    ThreefoldRepetition,
+    /// The position occurred five times.
+    FivefoldRepetition,
+}
+
+impl fmt::Display for Termination {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let reason = match self {
+            Termination::Checkmate => "checkmate",
+            Termination::Stalemate => "stalemate",
+            Termination::InsufficientMaterial => "insufficient material",
+            Termination::FiftyMoves => "fifty-move rule",
+            Termination::SeventyFiveMoves => "seventy-five-move rule",
+            Termination::ThreefoldRepetition => "threefold repetition",
+            Termination::FivefoldRepetition => "fivefold repetition",
+        };
+
+        write!(f, "{}", reason)
+    }
+}
+
+///
+/// The outcome of a game, being its winner and the reason it ended.
+///
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Outcome {
+    /// The side that won the game, or `None` if the game is drawn.
+    pub winner: Option<Color>,
+    /// The reason the game ended.
+    pub termination: Termination,
+}
+
+impl Outcome {
+    ///
+    /// Creates the outcome of a drawn game.
+    ///
+    /// # Arguments
+    ///
+    /// * `termination` - The reason the game is drawn.
+    ///
+    pub const fn draw(termination: Termination) -> Outcome {
+        Outcome {
+            winner: None,
+            termination,
+        }
+    }
+
+    ///
+    /// Get the result of the game, as written in the `Result` tag of a PGN file.
+    ///
+    pub const fn result(self) -> GameResult {
+        match self.winner {
+            Some(Color::White) => GameResult::WhiteWins,
+            Some(Color::Black) => GameResult::BlackWins,
+            None => GameResult::Draw,
+        }
+    }
+}
+
+impl From<Outcome> for GameResult {
+    fn from(outcome: Outcome) -> GameResult {
+        outcome.result()
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.winner {
+            Some(winner) => write!(f, "{} wins by {}", winner, self.termination),
+            None => write!(f, "draw by {}", self.termination),
+        }
+    }
+}
+
+///
+/// The status of a game in a position.
+///
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Status {
+    /// The game goes on.
+    Ongoing,
+    /// The game goes on, but a player can claim a draw.
+    Claimable(Outcome),
+    /// The game is over.
+    Over(Outcome),
+}
+
+impl Status {
+    ///
+    /// Whether the game is over.
+    ///
+    pub const fn is_over(self) -> bool {
+        matches!(self, Status::Over(_))
+    }
+
+    ///
+    /// Get the outcome of the game if it is over.
+    ///
+    pub const fn outcome(self) -> Option<Outcome> {
+        match self {
+            Status::Over(outcome) => Some(outcome),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Get the result of the game, as written in the `Result` tag of a PGN file.
+    ///
+    /// # Returns
+    ///
+    /// The result of the outcome if the game is over, or `GameResult::Unknown` if it goes on.
+    ///
+    pub const fn result(self) -> GameResult {
+        match self {
+            Status::Over(outcome) => outcome.result(),
+            _ => GameResult::Unknown,
+        }
+    }
+}
+
+#[test]
+fn outcome_result() {
+    let mate = Outcome {
+        winner: Some(Color::Black),
+        termination: Termination::Checkmate,
+    };
+    let draw = Outcome::draw(Termination::FiftyMoves);
+
+    assert_eq!(GameResult::from(mate), GameResult::BlackWins);
+    assert_eq!(mate.to_string(), "black wins by checkmate");
+    assert_eq!(draw.result(), GameResult::Draw);
+    assert_eq!(draw.to_string(), "draw by fifty-move rule");
+    assert_eq!(Status::Over(mate).result(), GameResult::BlackWins);
+    assert_eq!(Status::Claimable(draw).result(), GameResult::Unknown);
+    assert_eq!(Status::Claimable(draw).outcome(), None);
+    assert!(!Status::Ongoing.is_over());
+}
@@ -0,0 +1,264 @@
+use crate::board::{Board, Move, Piece};
+use crate::color::Color;
+use crate::square::Square;
+use std::error::Error;
+use std::fmt;
+
+const SQUARE_MASK: u16 = 0x3f;
+const TO_SHIFT: u16 = 6;
+const FLAG_SHIFT: u16 = 12;
+
+const FLAG_NORMAL: u16 = 0;
+const FLAG_CASTLE: u16 = 1;
+const FLAG_EN_PASSANT: u16 = 2;
+const FLAG_PROMOTION_KNIGHT: u16 = 4;
+const FLAG_PROMOTION_BISHOP: u16 = 5;
+const FLAG_PROMOTION_ROOK: u16 = 6;
+const FLAG_PROMOTION_QUEEN: u16 = 7;
+
+///
+/// A move packed into 16 bits, for storing moves in hash tables and binary files.
+///
+/// Bits 0 to 5 hold the from position, bits 6 to 11 the to position and bits 12 to 15 the kind
+/// of the move: 0 for a normal move, 1 for castling, 2 for en passant and 4 to 7 for a
+/// promotion to a knight, bishop, rook or queen. Castling is stored as the move of the king.
+///
+/// The rook of a castling move stands in the corner on the side of the to position and the pawn
+/// captured en passant on the rank of the from position and the file of the to position. The
+/// color of a promoted piece is taken from the board the move is made on.
+///
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Default)]
+pub struct PackedMove(u16);
+
+impl PackedMove {
+    ///
+    /// Creates a packed move from its 16-bit representation.
+    ///
+    /// # Arguments
+    ///
+    /// * `bits` - The 16-bit representation, as returned by `bits`.
+    ///
+    pub const fn from_bits(bits: u16) -> PackedMove {
+        PackedMove(bits)
+    }
+
+    ///
+    /// Get the 16-bit representation of the move.
+    ///
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+
+    ///
+    /// Get the square the piece is moved from.
+    ///
+    pub const fn from_square(self) -> Square {
+        Square::new((self.0 & SQUARE_MASK) as u8)
+    }
+
+    ///
+    /// Get the square the piece is moved to.
+    ///
+    pub const fn to_square(self) -> Square {
+        Square::new(((self.0 >> TO_SHIFT) & SQUARE_MASK) as u8)
+    }
+
+    ///
+    /// Unpacks the move.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The board the move is made on, giving the color of a promoted piece.
+    ///
+    /// # Returns
+    ///
+    /// The unpacked move, or `None` if the kind bits do not describe a move.
+    ///
+    pub fn to_move(self, board: &Board) -> Option<Move> {
+        self.unpack(board.turn() == Color::White)
+    }
+
+    ///
+    /// Unpacks the move of a side.
+    ///
+    /// # Arguments
+    ///
+    /// * `white` - Whether the move is made by white, giving the color of a promoted piece.
+    ///
+    /// # Returns
+    ///
+    /// The unpacked move, or `None` if the kind bits do not describe a move.
+    ///
+    pub(crate) fn unpack(self, white: bool) -> Option<Move> {
+        let from = self.from_square();
+        let to = self.to_square();
+
+        let promotion = |white_piece: Piece, black_piece: Piece| {
+            let promotion = if white { white_piece } else { black_piece };
+            Some(Move::Promotion {
+                from,
+                to,
+                promotion,
+            })
+        };
+
+        match self.0 >> FLAG_SHIFT {
+            FLAG_NORMAL => Some(Move::Normal { from, to }),
+            FLAG_CASTLE => Some(Move::Castle {
+                from,
+                to,
+                rook: if to > from {
+                    Square::new(from.index() / 8 * 8 + 7)
+                } else {
+                    Square::new(from.index() / 8 * 8)
+                },
+            }),
+            FLAG_EN_PASSANT => Some(Move::EnPassant {
+                from,
+                to,
+                captured: Square::from_coords(to.file(), from.rank()),
+            }),
+            FLAG_PROMOTION_KNIGHT => promotion(Piece::WhiteKnight, Piece::BlackKnight),
+            FLAG_PROMOTION_BISHOP => promotion(Piece::WhiteBishop, Piece::BlackBishop),
+            FLAG_PROMOTION_ROOK => promotion(Piece::WhiteRook, Piece::BlackRook),
+            FLAG_PROMOTION_QUEEN => promotion(Piece::WhiteQueen, Piece::BlackQueen),
+            _ => None,
+        }
+    }
+}
+
+///
+/// An error returned when packing a promotion to a pawn or a king, which has no packed form.
+///
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct PackMoveError {
+    /// The piece the move promotes to.
+    pub promotion: Piece,
+}
+
+impl fmt::Display for PackMoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "can not pack a promotion to '{}'",
+            self.promotion.to_char()
+        )
+    }
+}
+
+impl Error for PackMoveError {}
+
+impl TryFrom<Move> for PackedMove {
+    type Error = PackMoveError;
+
+    ///
+    /// Packs a move.
+    ///
+    /// # Returns
+    ///
+    /// The packed move, or an error if the move is a promotion to a pawn or a king.
+    ///
+    fn try_from(m: Move) -> Result<PackedMove, PackMoveError> {
+        let (from, to, flag) = match m {
+            Move::Normal { from, to } => (from, to, FLAG_NORMAL),
+            Move::Castle { from, to, .. } => (from, to, FLAG_CASTLE),
+            Move::EnPassant { from, to, .. } => (from, to, FLAG_EN_PASSANT),
+            Move::Promotion {
+                from,
+                to,
+                promotion,
+            } => {
+                let flag = match promotion {
+                    Piece::WhiteKnight | Piece::BlackKnight => FLAG_PROMOTION_KNIGHT,
+                    Piece::WhiteBishop | Piece::BlackBishop => FLAG_PROMOTION_BISHOP,
+                    Piece::WhiteRook | Piece::BlackRook => FLAG_PROMOTION_ROOK,
+                    Piece::WhiteQueen | Piece::BlackQueen => FLAG_PROMOTION_QUEEN,
+                    Piece::WhitePawn | Piece::BlackPawn | Piece::WhiteKing | Piece::BlackKing => {
+                        return Err(PackMoveError { promotion })
+                    }
+                };
+
+                (from, to, flag)
+            }
+        };
+
+        Ok(PackedMove(
+            from.index() as u16 | (to.index() as u16) << TO_SHIFT | flag << FLAG_SHIFT,
+        ))
+    }
+}
+
+impl From<PackedMove> for u16 {
+    fn from(m: PackedMove) -> u16 {
+        m.0
+    }
+}
+
+#[test]
+fn packed_move_round_trip() {
+    let fens = [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 b kq - 0 1",
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        "r3k2r/8/8/8/3pP3/8/8/R3K2R b KQkq e3 0 1",
+    ];
+
+    for fen in fens {
+        let board = Board::new(fen);
+
+        for m in board.legal_moves() {
+            let packed = PackedMove::try_from(m).unwrap();
+
+            assert_eq!(packed.to_move(&board), Some(m), "{}", fen);
+            assert_eq!(PackedMove::from_bits(packed.bits()), packed);
+        }
+    }
+}
+
+#[test]
+fn packed_move_bits() {
+    // e2e4
+    let packed = PackedMove::try_from(Move::Normal {
+        from: Square::E2,
+        to: Square::E4,
+    })
+    .unwrap();
+    assert_eq!(packed.bits(), 12 | 28 << 6);
+    assert_eq!(
+        (packed.from_square(), packed.to_square()),
+        (Square::E2, Square::E4)
+    );
+
+    // a7a8=Q
+    let packed = PackedMove::try_from(Move::Promotion {
+        from: Square::A7,
+        to: Square::A8,
+        promotion: Piece::WhiteQueen,
+    })
+    .unwrap();
+    assert_eq!(packed.bits(), 48 | 56 << 6 | 7 << 12);
+
+    // unused kind bits
+    let board = Board::new("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+    assert_eq!(PackedMove::from_bits(3 << 12).to_move(&board), None);
+}
+
+#[test]
+fn packed_move_invalid_promotion() {
+    for promotion in [Piece::WhiteKing, Piece::BlackPawn] {
+        let packed = PackedMove::try_from(Move::Promotion {
+            from: Square::A7,
+            to: Square::A8,
+            promotion,
+        });
+
+        assert_eq!(packed, Err(PackMoveError { promotion }));
+    }
+    assert_eq!(
+        PackMoveError {
+            promotion: Piece::WhiteKing
+        }
+        .to_string(),
+        "can not pack a promotion to 'K'"
+    );
+}
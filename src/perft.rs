@@ -1,4 +1,5 @@
-use crate::board::{Board, Move};
+use crate::board::Board;
+use crate::movelist::MoveList;
 use std::thread;
 use std::sync::{Arc, Mutex};
 use std::collections::HashMap;
@@ -10,32 +11,24 @@ pub fn perft(board: &Board, depth: u8, max_depth: u8, num_threads: u8) -> u64 {
 
     let mut nodes = 0;
 
-    let moves = if board.white_turn {
-        board.possible_white()
-    } else {
-        board.possible_black()
-    };
+    let moves = board.legal_moves();
 
-    let chunks = moves.chunks(moves.len() / num_threads as usize);
+    let chunks = moves.chunks((moves.len() / num_threads as usize).max(1));
     let result = Arc::new(Mutex::new(HashMap::new()));
     let mut handles: Vec<_> = Vec::new();
 
     for chunk in chunks {
         let result = Arc::clone(&result);
-        let my_board = board.clone();
-        let my_chunk: Vec<Move> = chunk.iter().map(|m| (*m).clone()).collect();
+        let mut my_board = *board;
+        let my_chunk: MoveList = chunk.iter().copied().collect();
 
         let handle = thread::spawn(move || {
             let mut nodes = 0;
 
             for m in my_chunk {
-                match my_board.make_move(&m) {
-                    Ok(new_board) => {
-                        let num_nodes = perft_branch(&new_board, depth + 1, max_depth);
-                        nodes += num_nodes;
-                    }
-                    Err(_) => {}
-                }
+                let undo = my_board.make(m);
+                nodes += perft_branch(&mut my_board, depth + 1, max_depth);
+                my_board.unmake(m, undo);
             }
 
             let mut result = result.lock().unwrap();
@@ -56,28 +49,25 @@ pub fn perft(board: &Board, depth: u8, max_depth: u8, num_threads: u8) -> u64 {
     nodes
 }
 
-fn perft_branch(board: &Board, depth: u8, max_depth: u8) -> u64 {
+fn perft_branch(board: &mut Board, depth: u8, max_depth: u8) -> u64 {
     if depth == max_depth {
         return 1;
     }
 
-    let mut nodes = 0;
+    let moves = board.legal_moves();
+
+    // the moves are legal, so the leaves are counted without being made
+    if depth + 1 == max_depth {
+        return moves.len() as u64;
+    }
 
-    let moves = if board.white_turn {
-        board.possible_white()
-    } else {
-        board.possible_black()
-    };
+    let mut nodes = 0;
 
     for m in moves {
-        match board.make_move(&m) {
-            Ok(new_board) => {
-                let num_nodes = perft_branch(&new_board, depth + 1, max_depth);
-                nodes += num_nodes;
-            }
-            Err(_) => {}
-        }
+        let undo = board.make(m);
+        nodes += perft_branch(board, depth + 1, max_depth);
+        board.unmake(m, undo);
     }
 
     nodes
-}
\ No newline at end of file
+}
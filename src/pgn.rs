@@ -0,0 +1,1288 @@
+use crate::board::{Board, Move, STARTING_FEN};
+use crate::color::Color;
+use crate::fen::FenError;
+use crate::notation::MoveParseError;
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::time::Duration;
+
+///
+/// The names of the tags of the seven-tag roster, in the order of the PGN standard.
+///
+pub const SEVEN_TAG_ROSTER: [&str; 7] =
+    ["Event", "Site", "Date", "Round", "White", "Black", "Result"];
+
+///
+/// The result of a game, as written at the end of its movetext.
+///
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum GameResult {
+    /// White won the game, written as `1-0`.
+    WhiteWins,
+    /// Black won the game, written as `0-1`.
+    BlackWins,
+    /// The game is drawn, written as `1/2-1/2`.
+    Draw,
+    /// The game is in progress, abandoned or its result is unknown, written as `*`.
+    Unknown,
+}
+
+impl GameResult {
+    ///
+    /// Get the result from its PGN token.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - The token, being `1-0`, `0-1`, `1/2-1/2` or `*`.
+    ///
+    /// # Returns
+    ///
+    /// The result, or `None` if the token is not a result.
+    ///
+    pub fn from_token(token: &str) -> Option<GameResult> {
+        match token {
+            "1-0" => Some(GameResult::WhiteWins),
+            "0-1" => Some(GameResult::BlackWins),
+            "1/2-1/2" => Some(GameResult::Draw),
+            "*" => Some(GameResult::Unknown),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Get the PGN token of the result.
+    ///
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            GameResult::WhiteWins => "1-0",
+            GameResult::BlackWins => "0-1",
+            GameResult::Draw => "1/2-1/2",
+            GameResult::Unknown => "*",
+        }
+    }
+}
+
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+///
+/// A move of the movetext of a game, with its annotations.
+///
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PgnMove {
+    /// The move.
+    pub m: Move,
+    /// The Numeric Annotation Glyphs of the move, where the suffixes `!`, `?`, `!!`, `??`, `!?`
+    /// and `?!` are read as the glyphs 1 to 6.
+    pub nags: Vec<u8>,
+    /// The comments before the move, only found before the first move of a game or variation.
+    pub comments_before: Vec<String>,
+    /// The comments after the move.
+    pub comments_after: Vec<String>,
+    /// The variations replacing the move, each starting from the position before the move.
+    pub variations: Vec<Vec<PgnMove>>,
+}
+
+impl PgnMove {
+    ///
+    /// Creates a move without annotations.
+    ///
+    /// # Arguments
+    ///
+    /// * `m` - The move.
+    ///
+    pub fn new(m: Move) -> PgnMove {
+        PgnMove {
+            m,
+            nags: Vec::new(),
+            comments_before: Vec::new(),
+            comments_after: Vec::new(),
+            variations: Vec::new(),
+        }
+    }
+}
+
+///
+/// A game read from a PGN file.
+///
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PgnGame {
+    /// The tags of the game as name and value pairs, in the order of the file.
+    pub tags: Vec<(String, String)>,
+    /// The position the game starts from, given by the `FEN` tag or the starting position.
+    pub start: Board,
+    /// The moves of the main line.
+    pub moves: Vec<PgnMove>,
+    /// The result at the end of the movetext, or `None` if the movetext has no result.
+    pub result: Option<GameResult>,
+}
+
+impl PgnGame {
+    ///
+    /// Get the value of a tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the tag, as in `White` or `Result`.
+    ///
+    /// # Returns
+    ///
+    /// The value of the first tag with the name, or `None` if the game has no such tag.
+    ///
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag, _)| tag == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    ///
+    /// Get the position at the end of the main line.
+    ///
+    pub fn board(&self) -> Board {
+        let mut board = self.start;
+
+        for m in &self.moves {
+            board.make(m.m);
+        }
+
+        board
+    }
+}
+
+///
+/// The reason a game could not be read from a PGN file.
+///
+#[derive(Debug)]
+pub enum PgnErrorKind {
+    /// The file could not be read.
+    Io(io::Error),
+    /// The text is not valid PGN.
+    Syntax(String),
+    /// The `FEN` tag does not hold a valid FEN string.
+    InvalidFen(FenError),
+    /// A move of the movetext is malformed, illegal or ambiguous.
+    InvalidMove(MoveParseError),
+}
+
+impl fmt::Display for PgnErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PgnErrorKind::Io(error) => write!(f, "{}", error),
+            PgnErrorKind::Syntax(message) => write!(f, "{}", message),
+            PgnErrorKind::InvalidFen(error) => write!(f, "invalid FEN tag: {}", error),
+            PgnErrorKind::InvalidMove(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+///
+/// An error returned when a game can not be read from a PGN file.
+///
+#[derive(Debug)]
+pub struct PgnError {
+    /// The number of the game in the file, starting at 1.
+    pub game: usize,
+    /// The ply of the move at which the error occurred, starting at 1 for the first move of the
+    /// game, or `None` if the error did not occur at a move.
+    pub ply: Option<usize>,
+    /// The reason of the error.
+    pub kind: PgnErrorKind,
+}
+
+impl fmt::Display for PgnError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.ply {
+            Some(ply) => write!(f, "game {}, ply {}: {}", self.game, ply, self.kind),
+            None => write!(f, "game {}: {}", self.game, self.kind),
+        }
+    }
+}
+
+impl Error for PgnError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            PgnErrorKind::Io(error) => Some(error),
+            PgnErrorKind::Syntax(_) => None,
+            PgnErrorKind::InvalidFen(error) => Some(error),
+            PgnErrorKind::InvalidMove(error) => Some(error),
+        }
+    }
+}
+
+///
+/// The maximum nesting depth of variations read by a `PgnReader`.
+///
+const MAX_VARIATION_DEPTH: usize = 64;
+
+///
+/// A reader of the games of a PGN file.
+///
+/// The file is read line by line and one game at a time, so files of any size can be read. The
+/// reader is an iterator over the games of the file. A game that can not be read gives an error,
+/// after which the reader continues with the next game. Variations nested more than 64 deep are
+/// a syntax error.
+///
+pub struct PgnReader<R: BufRead> {
+    /// The reader of the file.
+    reader: R,
+    /// The current line of the file.
+    line: String,
+    /// The byte offset of the next character in the current line.
+    pos: usize,
+    /// The number of games started so far.
+    games: usize,
+    /// Whether the movetext of the current game has been reached.
+    in_movetext: bool,
+    /// Whether reading the file failed, which ends the iteration.
+    failed: bool,
+}
+
+impl PgnReader<BufReader<File>> {
+    ///
+    /// Opens a PGN file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the `.pgn` file.
+    ///
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<PgnReader<BufReader<File>>> {
+        Ok(PgnReader::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+impl<R: BufRead> PgnReader<R> {
+    ///
+    /// Creates a PGN reader reading from the given reader.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader of the PGN text, e.g. a file or a byte slice.
+    ///
+    pub fn new(reader: R) -> PgnReader<R> {
+        PgnReader {
+            reader,
+            line: String::new(),
+            pos: 0,
+            games: 0,
+            in_movetext: false,
+            failed: false,
+        }
+    }
+
+    ///
+    /// Reads the next game.
+    ///
+    /// # Returns
+    ///
+    /// The game, an error if the game can not be read, or `None` at the end of the file.
+    ///
+    pub fn read_game(&mut self) -> Option<Result<PgnGame, PgnError>> {
+        if self.failed {
+            return None;
+        }
+
+        match self.game() {
+            Ok(game) => game.map(Ok),
+            Err(error) => {
+                match error.kind {
+                    PgnErrorKind::Io(_) => self.failed = true,
+                    _ => self.failed = self.skip_game().is_err(),
+                }
+
+                Some(Err(error))
+            }
+        }
+    }
+
+    ///
+    /// Reads the tags and movetext of the next game.
+    ///
+    fn game(&mut self) -> Result<Option<PgnGame>, PgnError> {
+        self.skip_whitespace().map_err(|e| self.io_error(e))?;
+
+        if self.peek().map_err(|e| self.io_error(e))?.is_none() {
+            return Ok(None);
+        }
+
+        self.games += 1;
+        self.in_movetext = false;
+
+        // tag pairs
+        let mut tags: Vec<(String, String)> = Vec::new();
+
+        while self.peek().map_err(|e| self.io_error(e))? == Some('[') {
+            tags.push(self.tag()?);
+            self.skip_whitespace().map_err(|e| self.io_error(e))?;
+        }
+
+        self.in_movetext = true;
+
+        let start = match tags.iter().find(|(name, _)| name == "FEN") {
+            Some((_, fen)) => Board::from_fen(fen).map_err(|e| PgnError {
+                game: self.games,
+                ply: None,
+                kind: PgnErrorKind::InvalidFen(e),
+            })?,
+            None => Board::new(STARTING_FEN),
+        };
+
+        // movetext
+        let (moves, result) = self.moves(start, 1, 0)?;
+
+        Ok(Some(PgnGame {
+            tags,
+            start,
+            moves,
+            result,
+        }))
+    }
+
+    ///
+    /// Reads a tag pair, as in `[White "Morphy, Paul"]`.
+    ///
+    fn tag(&mut self) -> Result<(String, String), PgnError> {
+        let malformed = |reader: &PgnReader<R>| reader.syntax_error(None, "malformed tag pair");
+
+        self.bump();
+        self.skip_whitespace().map_err(|e| self.io_error(e))?;
+
+        let mut name = String::new();
+
+        while let Some(c) = self.peek_in_line() {
+            if !(c.is_ascii_alphanumeric() || c == '_') {
+                break;
+            }
+
+            name.push(c);
+            self.bump();
+        }
+
+        self.skip_whitespace_in_line();
+
+        if name.is_empty() || self.peek_in_line() != Some('"') {
+            return Err(malformed(self));
+        }
+
+        self.bump();
+
+        let mut value = String::new();
+
+        loop {
+            match self.peek_in_line() {
+                Some('"') => break,
+                Some('\\') => {
+                    self.bump();
+
+                    match self.peek_in_line() {
+                        Some(c @ ('"' | '\\')) => value.push(c),
+                        _ => return Err(malformed(self)),
+                    }
+                }
+                Some(c) => value.push(c),
+                None => return Err(malformed(self)),
+            }
+
+            self.bump();
+        }
+
+        self.bump();
+        self.skip_whitespace_in_line();
+
+        if self.peek_in_line() != Some(']') {
+            return Err(malformed(self));
+        }
+
+        self.bump();
+
+        Ok((name, value))
+    }
+
+    ///
+    /// Reads the moves of the main line or a variation, with their annotations.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The position before the first move.
+    /// * `ply` - The ply of the first move, starting at 1 for the first move of the game.
+    /// * `depth` - The nesting depth of the variation, or 0 for the main line. A variation ends
+    ///   with `)`.
+    ///
+    /// # Returns
+    ///
+    /// The moves and the result ending the main line, if any.
+    ///
+    fn moves(
+        &mut self,
+        board: Board,
+        ply: usize,
+        depth: usize,
+    ) -> Result<(Vec<PgnMove>, Option<GameResult>), PgnError> {
+        let variation = depth > 0;
+        let mut moves: Vec<PgnMove> = Vec::new();
+        let mut comments: Vec<String> = Vec::new();
+        let (mut board, mut before) = (board, board);
+        let mut ply = ply;
+
+        loop {
+            self.skip_whitespace().map_err(|e| self.io_error(e))?;
+
+            let c = match self.peek().map_err(|e| self.io_error(e))? {
+                Some(c) => c,
+                None if variation => return Err(self.syntax_error(None, "unterminated variation")),
+                None => return Ok((moves, None)),
+            };
+
+            match c {
+                // the tag pairs of the next game
+                '[' if !variation => return Ok((moves, None)),
+                '{' | ';' => {
+                    let comment = self.comment().map_err(|e| self.io_error(e))?;
+
+                    match moves.last_mut() {
+                        Some(last) => last.comments_after.push(comment),
+                        None => comments.push(comment),
+                    }
+                }
+                '(' => {
+                    if moves.is_empty() {
+                        return Err(self.syntax_error(None, "variation before the first move"));
+                    }
+                    if depth == MAX_VARIATION_DEPTH {
+                        return Err(self.syntax_error(None, "variations nested too deeply"));
+                    }
+
+                    self.bump();
+
+                    let (alternative, _) = self.moves(before, ply - 1, depth + 1)?;
+
+                    if let Some(last) = moves.last_mut() {
+                        last.variations.push(alternative);
+                    }
+                }
+                ')' if variation => {
+                    self.bump();
+                    return Ok((moves, None));
+                }
+                '$' => {
+                    self.bump();
+
+                    let nag = self.token().map_err(|e| self.io_error(e))?;
+                    let nag = nag.parse::<u8>().map_err(|_| {
+                        self.syntax_error(Some(ply - 1), "malformed annotation glyph")
+                    })?;
+
+                    match moves.last_mut() {
+                        Some(last) => last.nags.push(nag),
+                        None => {
+                            return Err(
+                                self.syntax_error(None, "annotation glyph before the first move")
+                            )
+                        }
+                    }
+                }
+                ')' | ']' | '}' | '[' => {
+                    return Err(self.syntax_error(None, &format!("unexpected '{}'", c)));
+                }
+                _ => {
+                    let token = self.token().map_err(|e| self.io_error(e))?;
+
+                    if let Some(result) = GameResult::from_token(&token) {
+                        if variation {
+                            return Err(self.syntax_error(None, "result inside a variation"));
+                        }
+
+                        return Ok((moves, Some(result)));
+                    }
+
+                    let san = strip_move_number(&token);
+
+                    if san.is_empty() {
+                        continue;
+                    }
+
+                    let m = board.parse_san(san).map_err(|e| PgnError {
+                        game: self.games,
+                        ply: Some(ply),
+                        kind: PgnErrorKind::InvalidMove(e),
+                    })?;
+
+                    before = board;
+                    board.make(m);
+                    ply += 1;
+
+                    let mut pgn_move = PgnMove::new(m);
+                    pgn_move.comments_before = std::mem::take(&mut comments);
+                    pgn_move.nags.extend(suffix_nag(san));
+                    moves.push(pgn_move);
+                }
+            }
+        }
+    }
+
+    ///
+    /// Reads a comment, either between braces or from a semicolon to the end of the line.
+    ///
+    /// # Returns
+    ///
+    /// The text of the comment without the delimiters and surrounding whitespace.
+    ///
+    fn comment(&mut self) -> io::Result<String> {
+        let mut comment = String::new();
+
+        if self.peek()? == Some(';') {
+            self.bump();
+            comment.push_str(&self.line[self.pos..]);
+            self.pos = self.line.len();
+        } else {
+            self.bump();
+
+            loop {
+                match self.peek()? {
+                    Some('}') | None => break,
+                    Some(c) => comment.push(c),
+                }
+
+                self.bump();
+            }
+
+            self.bump();
+        }
+
+        Ok(comment.trim().to_string())
+    }
+
+    ///
+    /// Reads a token of the movetext, ending at whitespace or a delimiter.
+    ///
+    fn token(&mut self) -> io::Result<String> {
+        let mut token = String::new();
+
+        while let Some(c) = self.peek()? {
+            if c.is_whitespace() || "{};()[]$".contains(c) {
+                break;
+            }
+
+            token.push(c);
+            self.bump();
+        }
+
+        Ok(token)
+    }
+
+    ///
+    /// Skips the rest of the current game after an error, up to the tag pairs of the next game.
+    ///
+    fn skip_game(&mut self) -> io::Result<()> {
+        let mut in_movetext = self.in_movetext;
+        self.pos = self.line.len();
+
+        while self.peek()?.is_some() {
+            let line = self.line[self.pos..].trim_start();
+
+            if line.starts_with('[') && in_movetext {
+                break;
+            }
+
+            in_movetext |= !line.is_empty() && !line.starts_with('[');
+            self.pos = self.line.len();
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Get the next character, reading the next line when the current line is consumed.
+    ///
+    /// Lines starting with `%` are escaped and skipped, as are invalid UTF-8 sequences.
+    ///
+    /// # Returns
+    ///
+    /// The next character, or `None` at the end of the file.
+    ///
+    fn peek(&mut self) -> io::Result<Option<char>> {
+        while self.pos >= self.line.len() {
+            let mut bytes: Vec<u8> = Vec::new();
+
+            if self.reader.read_until(b'\n', &mut bytes)? == 0 {
+                return Ok(None);
+            }
+
+            self.line = String::from_utf8_lossy(&bytes).into_owned();
+            self.pos = 0;
+
+            if self.line.starts_with('%') {
+                self.pos = self.line.len();
+            } else if self.line.starts_with('\u{feff}') {
+                self.pos = '\u{feff}'.len_utf8();
+            }
+        }
+
+        Ok(self.line[self.pos..].chars().next())
+    }
+
+    ///
+    /// Get the next character of the current line, without reading the next line.
+    ///
+    fn peek_in_line(&self) -> Option<char> {
+        self.line[self.pos..]
+            .chars()
+            .next()
+            .filter(|c| *c != '\n' && *c != '\r')
+    }
+
+    ///
+    /// Moves past the next character of the current line.
+    ///
+    fn bump(&mut self) {
+        if let Some(c) = self.line[self.pos..].chars().next() {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    ///
+    /// Skips whitespace, reading the next lines as needed.
+    ///
+    fn skip_whitespace(&mut self) -> io::Result<()> {
+        while self.peek()?.is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Skips whitespace in the current line.
+    ///
+    fn skip_whitespace_in_line(&mut self) {
+        while self.peek_in_line().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    fn io_error(&self, error: io::Error) -> PgnError {
+        PgnError {
+            game: self.games,
+            ply: None,
+            kind: PgnErrorKind::Io(error),
+        }
+    }
+
+    fn syntax_error(&self, ply: Option<usize>, message: &str) -> PgnError {
+        PgnError {
+            game: self.games,
+            ply,
+            kind: PgnErrorKind::Syntax(message.to_string()),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for PgnReader<R> {
+    type Item = Result<PgnGame, PgnError>;
+
+    fn next(&mut self) -> Option<Result<PgnGame, PgnError>> {
+        self.read_game()
+    }
+}
+
+///
+/// An evaluation of a position, written in a comment as `[%eval 0.35]` or `[%eval #-3]`.
+///
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Evaluation {
+    /// The advantage of white in centipawns, negative when black is better.
+    Centipawns(i32),
+    /// The number of moves to a mate, positive when white mates and negative when black mates.
+    Mate(i32),
+}
+
+impl fmt::Display for Evaluation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Evaluation::Centipawns(centipawns) => {
+                let sign = if *centipawns < 0 { "-" } else { "" };
+                let centipawns = centipawns.unsigned_abs();
+
+                write!(f, "{}{}.{:02}", sign, centipawns / 100, centipawns % 100)
+            }
+            Evaluation::Mate(moves) => write!(f, "#{}", moves),
+        }
+    }
+}
+
+///
+/// The annotations of a move written by a `PgnWriter`.
+///
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct MoveAnnotation {
+    /// The Numeric Annotation Glyphs of the move.
+    pub nags: Vec<u8>,
+    /// The evaluation of the position after the move.
+    pub eval: Option<Evaluation>,
+    /// The time left on the clock of the moving side after the move.
+    pub clock: Option<Duration>,
+    /// The comment after the move. A `}` in the comment would end it early, so it is written
+    /// as `)`.
+    pub comment: Option<String>,
+}
+
+impl MoveAnnotation {
+    ///
+    /// Get the text of the comment after the move, with the evaluation and clock time as
+    /// embedded commands.
+    ///
+    /// # Returns
+    ///
+    /// The text, or `None` if there is nothing to comment.
+    ///
+    fn comment_text(&self) -> Option<String> {
+        let mut parts: Vec<String> = Vec::new();
+
+        if let Some(eval) = self.eval {
+            parts.push(format!("[%eval {}]", eval));
+        }
+
+        if let Some(clock) = self.clock {
+            let seconds = clock.as_secs();
+            parts.push(format!(
+                "[%clk {}:{:02}:{:02}]",
+                seconds / 3600,
+                seconds / 60 % 60,
+                seconds % 60
+            ));
+        }
+
+        if let Some(comment) = &self.comment {
+            // a comment ends at the first closing brace
+            parts.push(comment.replace('}', ")"));
+        }
+
+        if parts.is_empty() {
+            None
+        } else {
+            Some(parts.join(" "))
+        }
+    }
+}
+
+///
+/// The maximum length of a line of movetext written by a `PgnWriter`.
+///
+const LINE_LENGTH: usize = 79;
+
+///
+/// A writer of a game in PGN.
+///
+/// The game is written in the PGN export format: the seven-tag roster followed by the other
+/// tags, the `SetUp` and `FEN` tags when the game does not start from the starting position and
+/// the movetext in SAN, wrapped to lines of at most 79 characters.
+///
+/// # Examples
+///
+/// ```
+/// use osprey::pgn::{GameResult, PgnWriter};
+/// use osprey::{Board, STARTING_FEN};
+///
+/// let mut writer = PgnWriter::new(Board::new(STARTING_FEN));
+/// writer.tag("White", "Morphy, Paul");
+///
+/// let board = writer.board();
+/// writer.push(board.parse_san("e4").unwrap()).unwrap();
+/// writer.set_result(GameResult::WhiteWins);
+///
+/// assert!(writer.to_string().ends_with("\n\n1. e4 1-0\n"));
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct PgnWriter {
+    /// The tags besides the seven-tag roster and the `SetUp` and `FEN` tags.
+    tags: Vec<(String, String)>,
+    /// The position the game starts from.
+    start: Board,
+    /// The position after the last move.
+    board: Board,
+    /// The moves of the game with their annotations.
+    moves: Vec<(Move, MoveAnnotation)>,
+    /// The result of the game.
+    result: GameResult,
+}
+
+impl PgnWriter {
+    ///
+    /// Creates a writer of a game without moves.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The position the game starts from.
+    ///
+    pub fn new(start: Board) -> PgnWriter {
+        PgnWriter {
+            tags: Vec::new(),
+            start,
+            board: start,
+            moves: Vec::new(),
+            result: GameResult::Unknown,
+        }
+    }
+
+    ///
+    /// Sets the value of a tag, replacing an earlier value.
+    ///
+    /// The `Result` tag is taken from the result of the game and the `SetUp` and `FEN` tags from
+    /// the start position, so setting them has no effect.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the tag, as in `White`.
+    /// * `value` - The value of the tag.
+    ///
+    pub fn tag(&mut self, name: &str, value: &str) -> &mut PgnWriter {
+        match self.tags.iter_mut().find(|(tag, _)| tag == name) {
+            Some((_, old)) => *old = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string())),
+        }
+
+        self
+    }
+
+    ///
+    /// Sets the result of the game, written as the `Result` tag and at the end of the movetext.
+    ///
+    pub fn set_result(&mut self, result: GameResult) -> &mut PgnWriter {
+        self.result = result;
+        self
+    }
+
+    ///
+    /// Get the position after the last move.
+    ///
+    pub fn board(&self) -> Board {
+        self.board
+    }
+
+    ///
+    /// Adds a move without annotations.
+    ///
+    /// # Arguments
+    ///
+    /// * `m` - The move, which should be legal after the previous moves.
+    ///
+    /// # Returns
+    ///
+    /// An error if the move is not legal.
+    ///
+    pub fn push(&mut self, m: Move) -> Result<(), MoveParseError> {
+        self.push_annotated(m, MoveAnnotation::default())
+    }
+
+    ///
+    /// Adds a move with its annotations.
+    ///
+    /// # Arguments
+    ///
+    /// * `m` - The move, which should be legal after the previous moves.
+    /// * `annotation` - The annotations of the move.
+    ///
+    /// # Returns
+    ///
+    /// An error if the move is not legal.
+    ///
+    pub fn push_annotated(
+        &mut self,
+        m: Move,
+        annotation: MoveAnnotation,
+    ) -> Result<(), MoveParseError> {
+        if !self.board.legal_moves().contains(&m) {
+            return Err(MoveParseError::Illegal(m.to_uci()));
+        }
+
+        self.board.make(m);
+        self.moves.push((m, annotation));
+
+        Ok(())
+    }
+
+    ///
+    /// Writes the game.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer to write the PGN text to.
+    ///
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        write!(writer, "{}", self)
+    }
+
+    ///
+    /// Get the tags of the game in the order they are written.
+    ///
+    fn all_tags(&self) -> Vec<(String, String)> {
+        let mut tags: Vec<(String, String)> = Vec::new();
+
+        for name in SEVEN_TAG_ROSTER {
+            let value = match name {
+                "Result" => self.result.as_str(),
+                _ => self
+                    .tags
+                    .iter()
+                    .find(|(tag, _)| tag == name)
+                    .map(|(_, value)| value.as_str())
+                    .unwrap_or(if name == "Date" { "????.??.??" } else { "?" }),
+            };
+
+            tags.push((name.to_string(), value.to_string()));
+        }
+
+        if self.start != Board::new(STARTING_FEN) {
+            tags.push(("SetUp".to_string(), "1".to_string()));
+            tags.push(("FEN".to_string(), self.start.export_fen()));
+        }
+
+        for (name, value) in &self.tags {
+            if !SEVEN_TAG_ROSTER.contains(&name.as_str()) && name != "SetUp" && name != "FEN" {
+                tags.push((name.clone(), value.clone()));
+            }
+        }
+
+        tags
+    }
+
+    ///
+    /// Get the tokens of the movetext, being moves with their move numbers, glyphs, the words of
+    /// comments and the result.
+    ///
+    fn movetext(&self) -> Vec<String> {
+        let mut tokens: Vec<String> = Vec::new();
+        let mut board = self.start;
+
+        // black moves are numbered at the start and after a comment
+        let mut number_black = true;
+
+        for (m, annotation) in &self.moves {
+            // a move number is kept on the line of its move
+            let san = board.to_san(*m);
+
+            if board.turn() == Color::White {
+                tokens.push(format!("{}. {}", board.fullmove(), san));
+            } else if number_black {
+                tokens.push(format!("{}... {}", board.fullmove(), san));
+            } else {
+                tokens.push(san);
+            }
+
+            tokens.extend(annotation.nags.iter().map(|nag| format!("${}", nag)));
+
+            number_black = false;
+
+            if let Some(comment) = annotation.comment_text() {
+                let words: Vec<&str> = comment.split_whitespace().collect();
+
+                match words.as_slice() {
+                    [] => tokens.push("{}".to_string()),
+                    [word] => tokens.push(format!("{{{}}}", word)),
+                    [first, middle @ .., last] => {
+                        tokens.push(format!("{{{}", first));
+                        tokens.extend(middle.iter().map(|word| word.to_string()));
+                        tokens.push(format!("{}}}", last));
+                    }
+                }
+
+                number_black = true;
+            }
+
+            board.make(*m);
+        }
+
+        tokens.push(self.result.to_string());
+        tokens
+    }
+}
+
+impl fmt::Display for PgnWriter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (name, value) in self.all_tags() {
+            let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+            writeln!(f, "[{} \"{}\"]", name, value)?;
+        }
+
+        writeln!(f)?;
+
+        let mut length = 0;
+
+        for token in self.movetext() {
+            if length > 0 && length + 1 + token.len() > LINE_LENGTH {
+                writeln!(f)?;
+                length = 0;
+            }
+
+            if length > 0 {
+                write!(f, " ")?;
+                length += 1;
+            }
+
+            write!(f, "{}", token)?;
+            length += token.len();
+        }
+
+        writeln!(f)
+    }
+}
+
+///
+/// Removes a move number from a movetext token.
+///
+/// # Arguments
+///
+/// * `token` - The token, as in `12.`, `12...`, `12.e4` or `e4`.
+///
+/// # Returns
+///
+/// The rest of the token, which is empty if the token is only a move number.
+///
+fn strip_move_number(token: &str) -> &str {
+    let rest = token.trim_start_matches(|c: char| c.is_ascii_digit());
+
+    // castling with zeros also starts with a digit
+    if rest.is_empty() || rest.len() == token.len() || rest.starts_with('.') {
+        rest.trim_start_matches('.')
+    } else {
+        token
+    }
+}
+
+///
+/// Get the Numeric Annotation Glyph of the move suffix of a SAN move, as in `e4!?`.
+///
+/// # Arguments
+///
+/// * `san` - The move.
+///
+/// # Returns
+///
+/// The glyph, or `None` if the move has no known suffix.
+///
+fn suffix_nag(san: &str) -> Option<u8> {
+    let annotation = &san[san.trim_end_matches(['!', '?']).len()..];
+
+    match annotation {
+        "!" => Some(1),
+        "?" => Some(2),
+        "!!" => Some(3),
+        "??" => Some(4),
+        "!?" => Some(5),
+        "?!" => Some(6),
+        _ => None,
+    }
+}
+
+#[test]
+fn move_numbers() {
+    assert_eq!(strip_move_number("12."), "");
+    assert_eq!(strip_move_number("12..."), "");
+    assert_eq!(strip_move_number("..."), "");
+    assert_eq!(strip_move_number("12.e4"), "e4");
+    assert_eq!(strip_move_number("e4"), "e4");
+    assert_eq!(strip_move_number("0-0-0"), "0-0-0");
+}
+
+#[test]
+fn read_annotations() {
+    let pgn = "[Event \"Test \\\"quoted\\\"\"]\n\
+               [FEN \"4k3/8/8/8/8/8/4P3/4K3 w - - 0 1\"]\n\
+               \n\
+               {Start} 1. e4!? $14 {After e4} (1. e3 ; a quiet move\n\
+               1... Kd7 (1... Ke7 2. Kd2) 2. Kd2) 1... Kd8 $2 *\n";
+
+    let games: Vec<PgnGame> = PgnReader::new(pgn.as_bytes())
+        .collect::<Result<Vec<PgnGame>, PgnError>>()
+        .unwrap();
+
+    assert_eq!(games.len(), 1);
+
+    let game = &games[0];
+    assert_eq!(game.tag("Event"), Some("Test \"quoted\""));
+    assert_eq!(game.result, Some(GameResult::Unknown));
+    assert_eq!(game.moves.len(), 2);
+
+    let e4 = &game.moves[0];
+    assert_eq!(game.start.to_san(e4.m), "e4");
+    assert_eq!(e4.nags, vec![5, 14]);
+    assert_eq!(e4.comments_before, vec!["Start"]);
+    assert_eq!(e4.comments_after, vec!["After e4"]);
+    assert_eq!(e4.variations.len(), 1);
+
+    let variation = &e4.variations[0];
+    assert_eq!(variation.len(), 3);
+    assert_eq!(variation[0].comments_after, vec!["a quiet move"]);
+    assert_eq!(variation[1].variations[0].len(), 2);
+
+    assert_eq!(game.moves[1].nags, vec![2]);
+    assert_eq!(game.board().export_fen(), "3k4/8/8/8/4P3/8/8/4K3 w - - 1 2");
+}
+
+#[test]
+fn read_errors() {
+    let pgn = "[Event \"Illegal\"]\n\n1. e4 e5 2. Ke3 Nc6 1-0\n\n\
+               [Event \"Bad tag]\n\n1. d4 *\n\n\
+               [Event \"Unterminated\"]\n\n1. d4 (1. e4 e5 *\n\n\
+               [Event \"Bad FEN\"]\n[FEN \"8/8 w - - 0 1\"]\n\n*\n\n\
+               [Event \"Good\"]\n\n1. c4 1/2-1/2\n";
+
+    let games: Vec<Result<PgnGame, PgnError>> = PgnReader::new(pgn.as_bytes()).collect();
+    assert_eq!(games.len(), 5);
+
+    let error = games[0].as_ref().unwrap_err();
+    assert_eq!((error.game, error.ply), (1, Some(3)));
+    assert!(matches!(
+        error.kind,
+        PgnErrorKind::InvalidMove(MoveParseError::Illegal(_))
+    ));
+    assert_eq!(error.to_string(), "game 1, ply 3: illegal move 'Ke3'");
+
+    let error = games[1].as_ref().unwrap_err();
+    assert_eq!((error.game, error.ply), (2, None));
+    assert!(matches!(error.kind, PgnErrorKind::Syntax(_)));
+
+    let error = games[2].as_ref().unwrap_err();
+    assert_eq!(error.game, 3);
+    assert!(matches!(error.kind, PgnErrorKind::Syntax(_)));
+
+    let error = games[3].as_ref().unwrap_err();
+    assert_eq!(error.game, 4);
+    assert!(matches!(error.kind, PgnErrorKind::InvalidFen(_)));
+
+    let game = games[4].as_ref().unwrap();
+    assert_eq!(game.tag("Event"), Some("Good"));
+    assert_eq!(game.result, Some(GameResult::Draw));
+}
+
+#[test]
+fn read_deep_variations() {
+    let nested = |depth: usize| {
+        format!(
+            "1. e4 {}e5 {}*\n\n",
+            "(1. d4 ".repeat(depth),
+            ")".repeat(depth)
+        )
+    };
+    let pgn = format!(
+        "{}{}[Event \"Good\"]\n\n1. c4 *\n",
+        nested(64),
+        nested(200_000)
+    );
+
+    let games: Vec<Result<PgnGame, PgnError>> = PgnReader::new(pgn.as_bytes()).collect();
+    assert_eq!(games.len(), 3);
+
+    let mut line = &games[0].as_ref().unwrap().moves;
+    for _ in 0..64 {
+        line = &line[0].variations[0];
+    }
+    assert_eq!(line.len(), 2);
+
+    let error = games[1].as_ref().unwrap_err();
+    assert_eq!(error.game, 2);
+    assert!(matches!(error.kind, PgnErrorKind::Syntax(_)));
+    assert_eq!(error.to_string(), "game 2: variations nested too deeply");
+
+    assert_eq!(games[2].as_ref().unwrap().tag("Event"), Some("Good"));
+}
+
+#[test]
+fn write_black_to_move() {
+    let start = Board::new("4k3/8/8/8/8/8/4P3/4K3 b - - 3 40");
+    let mut writer = PgnWriter::new(start);
+    writer
+        .tag("Event", "Endgame \"study\"")
+        .tag("Annotator", "Osprey")
+        .set_result(GameResult::Draw);
+
+    for san in ["Kd7", "e4", "Kc6", "e5"] {
+        let m = writer.board().parse_san(san).unwrap();
+        writer.push(m).unwrap();
+    }
+
+    assert_eq!(
+        writer.to_string(),
+        "[Event \"Endgame \\\"study\\\"\"]\n\
+         [Site \"?\"]\n\
+         [Date \"????.??.??\"]\n\
+         [Round \"?\"]\n\
+         [White \"?\"]\n\
+         [Black \"?\"]\n\
+         [Result \"1/2-1/2\"]\n\
+         [SetUp \"1\"]\n\
+         [FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 3 40\"]\n\
+         [Annotator \"Osprey\"]\n\
+         \n\
+         40... Kd7 41. e4 Kc6 42. e5 1/2-1/2\n"
+    );
+
+    let illegal = writer.board().parse_san("Kc6");
+    assert!(illegal.is_err());
+
+    let m = Board::new(STARTING_FEN).parse_san("e4").unwrap();
+    assert_eq!(
+        writer.push(m),
+        Err(MoveParseError::Illegal("e2e4".to_string()))
+    );
+}
+
+#[test]
+fn write_annotations() {
+    let mut writer = PgnWriter::new(Board::new(STARTING_FEN));
+
+    let annotations = [
+        MoveAnnotation {
+            nags: vec![1],
+            eval: Some(Evaluation::Centipawns(35)),
+            clock: Some(Duration::from_secs(3 * 3600 + 5)),
+            comment: Some("Best by test".to_string()),
+        },
+        MoveAnnotation {
+            eval: Some(Evaluation::Centipawns(-5)),
+            ..MoveAnnotation::default()
+        },
+        MoveAnnotation {
+            nags: vec![2, 18],
+            eval: Some(Evaluation::Mate(-1)),
+            ..MoveAnnotation::default()
+        },
+        MoveAnnotation {
+            comment: Some("Not {best}".to_string()),
+            ..MoveAnnotation::default()
+        },
+    ];
+
+    for (san, annotation) in ["e4", "e5", "Ke2", "Nc6"].iter().zip(annotations) {
+        let m = writer.board().parse_san(san).unwrap();
+        writer.push_annotated(m, annotation).unwrap();
+    }
+
+    let pgn = writer.to_string();
+    let movetext = pgn.split("\n\n").nth(1).unwrap();
+
+    assert_eq!(
+        movetext,
+        "1. e4 $1 {[%eval 0.35] [%clk 3:00:05] Best by test} 1... e5 {[%eval -0.05]}\n\
+         2. Ke2 $2 $18 {[%eval #-1]} 2... Nc6 {Not {best)} *\n"
+    );
+    assert!(pgn.lines().all(|line| line.len() <= LINE_LENGTH));
+
+    // the annotations are read back as comments and glyphs
+    let game = PgnReader::new(pgn.as_bytes()).next().unwrap().unwrap();
+    assert_eq!(game.moves.len(), 4);
+    assert_eq!(game.moves[0].nags, vec![1]);
+    assert_eq!(
+        game.moves[0].comments_after,
+        vec!["[%eval 0.35] [%clk 3:00:05] Best by test"]
+    );
+    assert_eq!(game.moves[2].nags, vec![2, 18]);
+    assert_eq!(game.moves[3].comments_after, vec!["Not {best)"]);
+}
@@ -0,0 +1,299 @@
+use crate::board::{Board, Move, Piece};
+use crate::color::Color;
+use crate::packedmove::PackedMove;
+use crate::square::{File, Rank, Square};
+use serde::de::{Error, Unexpected};
+use serde::{ser, Deserialize, Deserializer, Serialize, Serializer};
+
+///
+/// Serializes the board as its FEN string.
+///
+impl Serialize for Board {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.export_fen())
+    }
+}
+
+impl<'de> Deserialize<'de> for Board {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Board, D::Error> {
+        let fen = String::deserialize(deserializer)?;
+
+        Board::from_fen(&fen).map_err(D::Error::custom)
+    }
+}
+
+///
+/// Serializes the move as UCI text in human-readable formats, and as the 16 bits of its
+/// `PackedMove` in compact formats. A promotion to a pawn or a king has no packed form, so
+/// serializing it in a compact format gives an error.
+///
+/// UCI text does not say which piece moves, so reading it back without a board is lossy. Every
+/// move from e1 to g1 or c1 and from e8 to g8 or c8 is read as castling, even when a rook or a
+/// queen made it, and every other move without a promotion letter is read as a normal move.
+/// Such rook and queen moves and en passant captures only keep their kind in compact formats.
+/// The color of a promoted piece is white on the eighth rank and black on the first.
+///
+/// To restore the exact move, resolve it against the board it is made on with
+/// `Board::parse_uci_move(&m.to_uci())`.
+///
+impl Serialize for Move {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_uci())
+        } else {
+            let packed = PackedMove::try_from(*self).map_err(ser::Error::custom)?;
+
+            serializer.serialize_u16(packed.bits())
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Move {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Move, D::Error> {
+        if deserializer.is_human_readable() {
+            let uci = String::deserialize(deserializer)?;
+
+            move_from_uci(&uci)
+                .ok_or_else(|| D::Error::invalid_value(Unexpected::Str(&uci), &"a UCI move"))
+        } else {
+            let bits = u16::deserialize(deserializer)?;
+            let packed = PackedMove::from_bits(bits);
+
+            packed
+                .unpack(packed.to_square().rank() != Rank::First)
+                .ok_or_else(|| {
+                    D::Error::invalid_value(Unexpected::Unsigned(bits as u64), &"a packed move")
+                })
+        }
+    }
+}
+
+///
+/// Reads a move from UCI text without a board, taking the moves a castling king makes as
+/// castling.
+///
+/// # Arguments
+///
+/// * `input` - The move, as in `e2e4`, `e1g1` or `e7e8q`.
+///
+/// # Returns
+///
+/// The move, or `None` if the text is not a UCI move.
+///
+fn move_from_uci(input: &str) -> Option<Move> {
+    if !input.is_ascii() || !(4..=5).contains(&input.len()) {
+        return None;
+    }
+
+    let from: Square = input[0..2].parse().ok()?;
+    let to: Square = input[2..4].parse().ok()?;
+
+    if let Some(letter) = input[4..].chars().next() {
+        let white = match to.rank() {
+            Rank::Eighth => true,
+            Rank::First => false,
+            _ => return None,
+        };
+
+        if !matches!(letter, 'n' | 'b' | 'r' | 'q') {
+            return None;
+        }
+
+        let promotion = Piece::from_char(if white {
+            letter.to_ascii_uppercase()
+        } else {
+            letter
+        })?;
+
+        return Some(Move::Promotion {
+            from,
+            to,
+            promotion,
+        });
+    }
+
+    let castling = matches!(
+        (from, to),
+        (Square::E1, Square::G1 | Square::C1) | (Square::E8, Square::G8 | Square::C8)
+    );
+
+    if castling {
+        let rook_file = if to.file() == File::G {
+            File::H
+        } else {
+            File::A
+        };
+
+        Some(Move::Castle {
+            from,
+            to,
+            rook: Square::from_coords(rook_file, from.rank()),
+        })
+    } else {
+        Some(Move::Normal { from, to })
+    }
+}
+
+///
+/// Serializes the piece as its FEN letter.
+///
+impl Serialize for Piece {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_char(self.to_char())
+    }
+}
+
+impl<'de> Deserialize<'de> for Piece {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Piece, D::Error> {
+        let letter = char::deserialize(deserializer)?;
+
+        Piece::from_char(letter)
+            .ok_or_else(|| D::Error::invalid_value(Unexpected::Char(letter), &"a piece letter"))
+    }
+}
+
+///
+/// Serializes the color as its FEN letter, `w` or `b`.
+///
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_char(self.to_char())
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        let letter = char::deserialize(deserializer)?;
+
+        Color::from_char(letter)
+            .ok_or_else(|| D::Error::invalid_value(Unexpected::Char(letter), &"a color letter"))
+    }
+}
+
+#[test]
+fn board_round_trip() {
+    let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+    let board = Board::new(fen);
+    let json = serde_json::to_string(&board).unwrap();
+
+    assert_eq!(json, format!("\"{}\"", fen));
+    assert_eq!(serde_json::from_str::<Board>(&json).unwrap(), board);
+    assert!(serde_json::from_str::<Board>("\"8/8/8 w - - 0 1\"").is_err());
+}
+
+#[test]
+fn move_round_trip() {
+    use serde_test::{assert_tokens, Configure, Token};
+
+    let normal = Move::Normal {
+        from: Square::E2,
+        to: Square::E4,
+    };
+    let castle = Move::Castle {
+        from: Square::E8,
+        to: Square::C8,
+        rook: Square::A8,
+    };
+    let promotion = Move::Promotion {
+        from: Square::B2,
+        to: Square::A1,
+        promotion: Piece::BlackKnight,
+    };
+    let en_passant = Move::EnPassant {
+        from: Square::E5,
+        to: Square::D6,
+        captured: Square::D5,
+    };
+
+    assert_tokens(&normal.readable(), &[Token::Str("e2e4")]);
+    assert_tokens(&castle.readable(), &[Token::Str("e8c8")]);
+    assert_tokens(&promotion.readable(), &[Token::Str("b2a1n")]);
+
+    for m in [normal, castle, promotion, en_passant] {
+        assert_tokens(
+            &m.compact(),
+            &[Token::U16(PackedMove::try_from(m).unwrap().bits())],
+        );
+    }
+
+    let json = serde_json::to_string(&vec![normal, castle, promotion]).unwrap();
+    assert_eq!(json, r#"["e2e4","e8c8","b2a1n"]"#);
+    assert_eq!(
+        serde_json::from_str::<Vec<Move>>(&json).unwrap(),
+        vec![normal, castle, promotion]
+    );
+
+    // en passant reads back as a normal move from UCI text
+    assert_eq!(
+        serde_json::from_str::<Move>("\"e5d6\"").unwrap(),
+        Move::Normal {
+            from: Square::E5,
+            to: Square::D6,
+        }
+    );
+
+    // a rook move from e1 to g1 reads back as castling, until resolved against its board
+    let rook_move = Move::Normal {
+        from: Square::E1,
+        to: Square::G1,
+    };
+    let json = serde_json::to_string(&rook_move).unwrap();
+    let read = serde_json::from_str::<Move>(&json).unwrap();
+    assert_eq!(
+        read,
+        Move::Castle {
+            from: Square::E1,
+            to: Square::G1,
+            rook: Square::H1,
+        }
+    );
+    let board = Board::new("4k3/8/8/8/8/8/8/4R2K w - - 0 1");
+    assert_eq!(board.parse_uci_move(&read.to_uci()), Ok(rook_move));
+    assert_tokens(
+        &rook_move.compact(),
+        &[Token::U16(PackedMove::try_from(rook_move).unwrap().bits())],
+    );
+
+    for invalid in ["\"e2\"", "\"e2e9\"", "\"e2e4q\"", "\"e7e8k\"", "\"e7e8Q\""] {
+        assert!(
+            serde_json::from_str::<Move>(invalid).is_err(),
+            "{}",
+            invalid
+        );
+    }
+}
+
+#[test]
+fn move_without_packed_form() {
+    use serde_test::{assert_ser_tokens_error, Configure};
+
+    // a promotion to a king can be built, but not packed
+    let promotion = Move::Promotion {
+        from: Square::E7,
+        to: Square::E8,
+        promotion: Piece::WhiteKing,
+    };
+
+    assert_ser_tokens_error(&promotion.compact(), &[], "can not pack a promotion to 'K'");
+}
+
+#[test]
+fn piece_and_color_round_trip() {
+    use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+    for piece in Piece::ALL {
+        assert_tokens(&piece, &[Token::Char(piece.to_char())]);
+    }
+    assert_tokens(&Color::White, &[Token::Char('w')]);
+    assert_tokens(&Color::Black, &[Token::Char('b')]);
+
+    assert_de_tokens_error::<Piece>(
+        &[Token::Char('x')],
+        "invalid value: character `x`, expected a piece letter",
+    );
+    assert_eq!(serde_json::to_string(&Piece::BlackQueen).unwrap(), "\"q\"");
+    assert_eq!(
+        serde_json::from_str::<Color>("\"b\"").unwrap(),
+        Color::Black
+    );
+}
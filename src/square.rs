@@ -0,0 +1,452 @@
+use crate::bitboard::Bitboard;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+///
+/// A file of the board, from the a-file to the h-file.
+///
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+pub enum File {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+}
+
+impl File {
+    ///
+    /// All files, from the a-file to the h-file.
+    ///
+    pub const ALL: [File; 8] = [
+        File::A,
+        File::B,
+        File::C,
+        File::D,
+        File::E,
+        File::F,
+        File::G,
+        File::H,
+    ];
+
+    ///
+    /// Get the file with the given index.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the file, 0 for the a-file and 7 for the h-file.
+    ///
+    /// # Returns
+    ///
+    /// The file, or `None` if the index is out of range.
+    ///
+    pub const fn from_index(index: u8) -> Option<File> {
+        if index < 8 {
+            Some(File::ALL[index as usize])
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// Get the file with the given letter.
+    ///
+    /// # Arguments
+    ///
+    /// * `c` - The lowercase letter of the file, from `a` to `h`.
+    ///
+    /// # Returns
+    ///
+    /// The file, or `None` if the letter is not a file.
+    ///
+    pub const fn from_char(c: char) -> Option<File> {
+        match c {
+            'a'..='h' => File::from_index(c as u8 - b'a'),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Get the index of the file, 0 for the a-file and 7 for the h-file.
+    ///
+    pub const fn index(self) -> u8 {
+        self as u8
+    }
+
+    ///
+    /// Get the lowercase letter of the file.
+    ///
+    pub const fn char(self) -> char {
+        (b'a' + self as u8) as char
+    }
+
+    ///
+    /// Get a bitboard of all squares on the file.
+    ///
+    pub const fn bitboard(self) -> Bitboard {
+        Bitboard(0x0101_0101_0101_0101 << self as u8)
+    }
+}
+
+impl fmt::Display for File {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.char())
+    }
+}
+
+///
+/// A rank of the board, from the first rank to the eighth rank.
+///
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+pub enum Rank {
+    First,
+    Second,
+    Third,
+    Fourth,
+    Fifth,
+    Sixth,
+    Seventh,
+    Eighth,
+}
+
+impl Rank {
+    ///
+    /// All ranks, from the first rank to the eighth rank.
+    ///
+    pub const ALL: [Rank; 8] = [
+        Rank::First,
+        Rank::Second,
+        Rank::Third,
+        Rank::Fourth,
+        Rank::Fifth,
+        Rank::Sixth,
+        Rank::Seventh,
+        Rank::Eighth,
+    ];
+
+    ///
+    /// Get the rank with the given index.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the rank, 0 for the first rank and 7 for the eighth rank.
+    ///
+    /// # Returns
+    ///
+    /// The rank, or `None` if the index is out of range.
+    ///
+    pub const fn from_index(index: u8) -> Option<Rank> {
+        if index < 8 {
+            Some(Rank::ALL[index as usize])
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// Get the rank with the given digit.
+    ///
+    /// # Arguments
+    ///
+    /// * `c` - The digit of the rank, from `1` to `8`.
+    ///
+    /// # Returns
+    ///
+    /// The rank, or `None` if the digit is not a rank.
+    ///
+    pub const fn from_char(c: char) -> Option<Rank> {
+        match c {
+            '1'..='8' => Rank::from_index(c as u8 - b'1'),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Get the index of the rank, 0 for the first rank and 7 for the eighth rank.
+    ///
+    pub const fn index(self) -> u8 {
+        self as u8
+    }
+
+    ///
+    /// Get the digit of the rank.
+    ///
+    pub const fn char(self) -> char {
+        (b'1' + self as u8) as char
+    }
+
+    ///
+    /// Get a bitboard of all squares on the rank.
+    ///
+    pub const fn bitboard(self) -> Bitboard {
+        Bitboard(0xff << (8 * self as u8))
+    }
+}
+
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.char())
+    }
+}
+
+///
+/// A square of the board.
+///
+/// Squares are numbered from 0 for a1 to 63 for h8, first from the a-file to the h-file and
+/// next from the first rank to the eighth rank, matching the bits of a `Bitboard`.
+///
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+pub struct Square(u8);
+
+impl Square {
+    pub const A1: Square = Square(0);
+    pub const B1: Square = Square(1);
+    pub const C1: Square = Square(2);
+    pub const D1: Square = Square(3);
+    pub const E1: Square = Square(4);
+    pub const F1: Square = Square(5);
+    pub const G1: Square = Square(6);
+    pub const H1: Square = Square(7);
+    pub const A2: Square = Square(8);
+    pub const B2: Square = Square(9);
+    pub const C2: Square = Square(10);
+    pub const D2: Square = Square(11);
+    pub const E2: Square = Square(12);
+    pub const F2: Square = Square(13);
+    pub const G2: Square = Square(14);
+    pub const H2: Square = Square(15);
+    pub const A3: Square = Square(16);
+    pub const B3: Square = Square(17);
+    pub const C3: Square = Square(18);
+    pub const D3: Square = Square(19);
+    pub const E3: Square = Square(20);
+    pub const F3: Square = Square(21);
+    pub const G3: Square = Square(22);
+    pub const H3: Square = Square(23);
+    pub const A4: Square = Square(24);
+    pub const B4: Square = Square(25);
+    pub const C4: Square = Square(26);
+    pub const D4: Square = Square(27);
+    pub const E4: Square = Square(28);
+    pub const F4: Square = Square(29);
+    pub const G4: Square = Square(30);
+    pub const H4: Square = Square(31);
+    pub const A5: Square = Square(32);
+    pub const B5: Square = Square(33);
+    pub const C5: Square = Square(34);
+    pub const D5: Square = Square(35);
+    pub const E5: Square = Square(36);
+    pub const F5: Square = Square(37);
+    pub const G5: Square = Square(38);
+    pub const H5: Square = Square(39);
+    pub const A6: Square = Square(40);
+    pub const B6: Square = Square(41);
+    pub const C6: Square = Square(42);
+    pub const D6: Square = Square(43);
+    pub const E6: Square = Square(44);
+    pub const F6: Square = Square(45);
+    pub const G6: Square = Square(46);
+    pub const H6: Square = Square(47);
+    pub const A7: Square = Square(48);
+    pub const B7: Square = Square(49);
+    pub const C7: Square = Square(50);
+    pub const D7: Square = Square(51);
+    pub const E7: Square = Square(52);
+    pub const F7: Square = Square(53);
+    pub const G7: Square = Square(54);
+    pub const H7: Square = Square(55);
+    pub const A8: Square = Square(56);
+    pub const B8: Square = Square(57);
+    pub const C8: Square = Square(58);
+    pub const D8: Square = Square(59);
+    pub const E8: Square = Square(60);
+    pub const F8: Square = Square(61);
+    pub const G8: Square = Square(62);
+    pub const H8: Square = Square(63);
+
+    ///
+    /// Get the square with the given index.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the square as a number between 0 and 63 (both included).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of range.
+    ///
+    #[inline]
+    pub const fn new(index: u8) -> Square {
+        assert!(index < 64, "square index out of range");
+        Square(index)
+    }
+
+    ///
+    /// Get the square with the given index.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the square as a number between 0 and 63 (both included).
+    ///
+    /// # Returns
+    ///
+    /// The square, or `None` if the index is out of range.
+    ///
+    pub const fn from_index(index: u8) -> Option<Square> {
+        if index < 64 {
+            Some(Square(index))
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// Get the square on the given file and rank.
+    ///
+    pub const fn from_coords(file: File, rank: Rank) -> Square {
+        Square(rank as u8 * 8 + file as u8)
+    }
+
+    ///
+    /// Get the index of the square as a number between 0 and 63 (both included).
+    ///
+    #[inline]
+    pub const fn index(self) -> u8 {
+        self.0
+    }
+
+    ///
+    /// Get the file of the square.
+    ///
+    pub const fn file(self) -> File {
+        File::ALL[(self.0 % 8) as usize]
+    }
+
+    ///
+    /// Get the rank of the square.
+    ///
+    pub const fn rank(self) -> Rank {
+        Rank::ALL[(self.0 / 8) as usize]
+    }
+
+    ///
+    /// Get a bitboard containing only the square.
+    ///
+    #[inline]
+    pub const fn bitboard(self) -> Bitboard {
+        Bitboard(1u64 << self.0)
+    }
+
+    ///
+    /// Get the square at an offset from the square.
+    ///
+    /// # Arguments
+    ///
+    /// * `files` - The number of files to move towards the h-file (negative towards the a-file).
+    /// * `ranks` - The number of ranks to move towards the eighth rank (negative towards the
+    ///   first rank).
+    ///
+    /// # Returns
+    ///
+    /// The square, or `None` if it is off the board.
+    ///
+    pub const fn offset(self, files: i8, ranks: i8) -> Option<Square> {
+        let file = (self.0 % 8) as i8 + files;
+        let rank = (self.0 / 8) as i8 + ranks;
+
+        if file >= 0 && file < 8 && rank >= 0 && rank < 8 {
+            Some(Square((rank * 8 + file) as u8))
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Square {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.file(), self.rank())
+    }
+}
+
+impl fmt::Debug for Square {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+///
+/// An error returned when parsing a string that is not the algebraic name of a square.
+///
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseSquareError;
+
+impl fmt::Display for ParseSquareError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "expected a square from a1 to h8")
+    }
+}
+
+impl Error for ParseSquareError {}
+
+impl FromStr for Square {
+    type Err = ParseSquareError;
+
+    fn from_str(input: &str) -> Result<Square, ParseSquareError> {
+        let mut chars = input.chars();
+
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(file), Some(rank), None) => {
+                match (File::from_char(file), Rank::from_char(rank)) {
+                    (Some(file), Some(rank)) => Ok(Square::from_coords(file, rank)),
+                    _ => Err(ParseSquareError),
+                }
+            }
+            _ => Err(ParseSquareError),
+        }
+    }
+}
+
+impl From<Square> for u8 {
+    fn from(square: Square) -> u8 {
+        square.0
+    }
+}
+
+impl TryFrom<u8> for Square {
+    type Error = ParseSquareError;
+
+    fn try_from(index: u8) -> Result<Square, ParseSquareError> {
+        Square::from_index(index).ok_or(ParseSquareError)
+    }
+}
+
+#[test]
+fn square_names() {
+    assert_eq!(Square::E4.to_string(), "e4");
+    assert_eq!("e4".parse::<Square>(), Ok(Square::E4));
+    assert_eq!("h8".parse::<Square>(), Ok(Square::H8));
+    assert_eq!("i1".parse::<Square>(), Err(ParseSquareError));
+    assert_eq!("a9".parse::<Square>(), Err(ParseSquareError));
+    assert_eq!("a1 ".parse::<Square>(), Err(ParseSquareError));
+
+    for index in 0..64 {
+        let square = Square::new(index);
+        assert_eq!(square.to_string().parse::<Square>(), Ok(square));
+    }
+}
+
+#[test]
+fn square_coords() {
+    assert_eq!(Square::E4.file(), File::E);
+    assert_eq!(Square::E4.rank(), Rank::Fourth);
+    assert_eq!(Square::from_coords(File::H, Rank::First), Square::H1);
+    assert_eq!(Square::E4.offset(1, 2), Some(Square::F6));
+    assert_eq!(Square::H1.offset(1, 0), None);
+    assert_eq!(Square::from_index(64), None);
+    assert_eq!(File::C.bitboard().count(), 8);
+    assert_eq!(Rank::Eighth.bitboard(), Bitboard(0xff00_0000_0000_0000));
+}
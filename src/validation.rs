@@ -0,0 +1,127 @@
+use crate::fen::FenError;
+use crate::square::Square;
+use std::error::Error;
+use std::fmt;
+
+///
+/// A reason why a parseable position is not a legal chess position.
+///
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum PositionIssue {
+    ///
+    /// A side does not have exactly one king.
+    ///
+    /// # Arguments
+    ///
+    /// * `white` - Whether the side is white.
+    /// * `count` - The number of kings the side has.
+    ///
+    KingCount { white: bool, count: u32 },
+    ///
+    /// A pawn stands on the first or eighth rank.
+    ///
+    /// # Arguments
+    ///
+    /// * `square` - The square of the pawn.
+    ///
+    PawnOnBackRank { square: Square },
+    ///
+    /// A side has a castling right while its king or the rook is not on its home square.
+    ///
+    /// # Arguments
+    ///
+    /// * `white` - Whether the side is white.
+    /// * `kingside` - Whether the castling right is kingside.
+    ///
+    InvalidCastlingRights { white: bool, kingside: bool },
+    ///
+    /// The en passant square does not belong to a pawn that could have just moved two squares.
+    ///
+    /// # Arguments
+    ///
+    /// * `square` - The en passant square.
+    ///
+    InvalidEnPassant { square: Square },
+    ///
+    /// The side not to move is in check.
+    ///
+    OpponentInCheck,
+}
+
+fn side_name(white: bool) -> &'static str {
+    if white {
+        "white"
+    } else {
+        "black"
+    }
+}
+
+impl fmt::Display for PositionIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PositionIssue::KingCount { white, count } => {
+                write!(f, "{} has {} kings instead of 1", side_name(*white), count)
+            }
+            PositionIssue::PawnOnBackRank { square } => {
+                write!(f, "pawn on {} is on a back rank", square)
+            }
+            PositionIssue::InvalidCastlingRights { white, kingside } => write!(
+                f,
+                "{} can castle {} but the king or rook is not on its home square",
+                side_name(*white),
+                if *kingside { "kingside" } else { "queenside" }
+            ),
+            PositionIssue::InvalidEnPassant { square } => write!(
+                f,
+                "no pawn can have just moved past en passant square {}",
+                square
+            ),
+            PositionIssue::OpponentInCheck => write!(f, "the side not to move is in check"),
+        }
+    }
+}
+
+///
+/// An error returned when constructing a board from a FEN string that is malformed or
+/// does not describe a legal chess position.
+///
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PositionError {
+    /// The FEN string is malformed.
+    Fen(FenError),
+    /// The FEN string describes a position with the given issues.
+    Illegal(Vec<PositionIssue>),
+}
+
+impl From<FenError> for PositionError {
+    fn from(error: FenError) -> PositionError {
+        PositionError::Fen(error)
+    }
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PositionError::Fen(error) => write!(f, "{}", error),
+            PositionError::Illegal(issues) => {
+                write!(f, "illegal position: ")?;
+                for (i, issue) in issues.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", issue)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for PositionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PositionError::Fen(error) => Some(error),
+            PositionError::Illegal(_) => None,
+        }
+    }
+}
@@ -0,0 +1,81 @@
+//!
+//! Zobrist keys used to hash a board.
+//!
+//! The key of a board is the XOR of the keys of all pieces on their squares, the side key if
+//! black is to move, the keys of all castling rights and the key of the en passant file if an
+//! en passant capture is possible.
+//!
+
+///
+/// The seed of the pseudo-random number generator used to generate the keys.
+///
+const SEED: u64 = 0x4f73_7072_6579_2121;
+
+///
+/// Generates pseudo-random keys using the SplitMix64 generator.
+///
+/// # Arguments
+///
+/// * `state` - The state of the generator. Each key is generated from a different state.
+///
+/// # Returns
+///
+/// An array of pseudo-random keys.
+///
+const fn generate<const N: usize>(mut state: u64) -> [u64; N] {
+    let mut keys = [0u64; N];
+    let mut i = 0;
+
+    while i < N {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        keys[i] = z ^ (z >> 31);
+
+        i += 1;
+    }
+
+    keys
+}
+
+const KEYS: [u64; 781] = generate(SEED);
+
+///
+/// Splits the generated keys into a key per piece and square.
+///
+const fn piece_keys() -> [[u64; 64]; 12] {
+    let mut keys = [[0u64; 64]; 12];
+    let mut i = 0;
+
+    while i < 12 * 64 {
+        keys[i / 64][i % 64] = KEYS[i];
+        i += 1;
+    }
+
+    keys
+}
+
+///
+/// The keys of the pieces, indexed by the piece (in the order of `Piece`) and the square.
+///
+pub const PIECES: [[u64; 64]; 12] = piece_keys();
+
+///
+/// The key used when black is to move.
+///
+pub const SIDE: u64 = KEYS[768];
+
+///
+/// The keys of the castling rights, in the order white kingside, white queenside, black kingside
+/// and black queenside.
+///
+pub const CASTLING: [u64; 4] = [KEYS[769], KEYS[770], KEYS[771], KEYS[772]];
+
+///
+/// The keys of the en passant files, from file a to file h.
+///
+pub const EN_PASSANT: [u64; 8] = [
+    KEYS[773], KEYS[774], KEYS[775], KEYS[776], KEYS[777], KEYS[778], KEYS[779], KEYS[780],
+];
@@ -0,0 +1,151 @@
+extern crate osprey;
+
+#[cfg(test)]
+mod legal_moves_tests {
+    use crate::osprey::{Board, Move, MoveKind};
+
+    ///
+    /// Get the legal moves by making every pseudo-legal move and rejecting those leaving the king
+    /// in check.
+    ///
+    fn filtered_moves(board: &Board) -> Vec<Move> {
+        board
+            .possible_moves(board.turn())
+            .into_iter()
+            .filter(|m| board.make_move(m).is_ok())
+            .collect()
+    }
+
+    ///
+    /// Compare the legal moves with the filtered pseudo-legal moves in every node of the tree.
+    ///
+    fn compare(board: &Board, depth: u8) {
+        let legal = board.legal_moves();
+        let filtered = filtered_moves(board);
+
+        assert_eq!(legal.len(), filtered.len(), "{}", board.export_fen());
+        for m in &legal {
+            assert!(filtered.contains(m), "{} {:?}", board.export_fen(), m);
+        }
+
+        if depth > 1 {
+            for m in legal {
+                compare(&board.make_move(&m).unwrap(), depth - 1);
+            }
+        }
+    }
+
+    const PERFT_POSITIONS: [&str; 6] = [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+    ];
+
+    #[test]
+    fn perft_positions() {
+        for fen in PERFT_POSITIONS {
+            compare(&Board::new(fen), 2);
+        }
+    }
+
+    ///
+    /// Compare the staged moves with the legal moves in every node of the tree.
+    ///
+    fn compare_stages(board: &Board, depth: u8) {
+        let fen = board.export_fen();
+        let legal = board.legal_moves();
+        let captures = board.generate(MoveKind::Captures);
+        let quiets = board.generate(MoveKind::Quiets);
+        let quiet_checks = board.generate(MoveKind::QuietChecks);
+        let evasions = board.generate(MoveKind::Evasions);
+
+        // the captures and quiet moves split the legal moves
+        assert_eq!(captures.len() + quiets.len(), legal.len(), "{}", fen);
+        for m in &legal {
+            assert!(
+                captures.contains(m) != quiets.contains(m),
+                "{} {:?}",
+                fen,
+                m
+            );
+        }
+
+        let expected_checks: Vec<Move> = quiets
+            .iter()
+            .copied()
+            .filter(|m| board.gives_check(*m))
+            .collect();
+        assert_eq!(quiet_checks.len(), expected_checks.len(), "{}", fen);
+        for m in &expected_checks {
+            assert!(quiet_checks.contains(m), "{} {:?}", fen, m);
+        }
+
+        if board.is_in_check() {
+            assert_eq!(evasions, legal, "{}", fen);
+        } else {
+            assert!(evasions.is_empty(), "{}", fen);
+        }
+
+        if depth > 1 {
+            for m in legal {
+                compare_stages(&board.make_move(&m).unwrap(), depth - 1);
+            }
+        }
+    }
+
+    #[test]
+    fn staged_generation() {
+        for fen in PERFT_POSITIONS {
+            compare_stages(&Board::new(fen), 3);
+        }
+
+        let quiet_checks = [
+            // an underpromoted rook checking through the square the pawn left
+            ("8/4P3/8/8/8/8/8/K3k3 w - - 0 1", 1),
+            // a pawn push discovering a check by the bishop
+            ("8/8/8/8/4k3/8/2P5/1B5K w - - 0 1", 2),
+            // castling and rook moves checking
+            ("5k2/8/8/8/8/8/8/4K2R w K - 0 1", 3),
+            // king moves discovering a check by the rook
+            ("8/8/8/8/8/8/k1K4R/8 w - - 0 1", 4),
+        ];
+
+        for (fen, checks) in quiet_checks {
+            let board = Board::new(fen);
+
+            assert_eq!(
+                board.generate(MoveKind::QuietChecks).len(),
+                checks,
+                "{}",
+                fen
+            );
+            compare_stages(&board, 2);
+        }
+    }
+
+    #[test]
+    fn tricky_positions() {
+        let positions = [
+            // en passant exposing the king along the rank
+            "8/8/8/KPp4r/8/8/8/6k1 w - c6 0 1",
+            "3k4/3p4/8/K1P4r/8/8/8/8 b - - 0 1",
+            // en passant capturing the checking pawn
+            "8/8/8/2k5/3Pp3/8/8/4K3 b - d3 0 1",
+            // en passant by a diagonally pinned pawn
+            "8/8/1k6/2b5/3pP3/8/8/6K1 b - e3 0 1",
+            // double check
+            "4k3/8/8/8/8/5n2/8/4K2r w - - 0 1",
+            // castling through an attacked square
+            "4k3/8/8/8/8/8/5r2/R3K2R w KQ - 0 1",
+            // pinned pieces
+            "4k3/4r3/8/8/8/1b6/2N5/K3R3 w - - 0 1",
+        ];
+
+        for fen in positions {
+            compare(&Board::new(fen), 4);
+        }
+    }
+}
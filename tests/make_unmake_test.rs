@@ -0,0 +1,64 @@
+extern crate osprey;
+
+#[cfg(test)]
+mod make_unmake_tests {
+    use crate::osprey::{Board, Move, Square};
+
+    ///
+    /// Make and unmake every legal move in every node of the tree, checking that making a move
+    /// in place gives the same board as copy-make and that unmaking it restores the board.
+    ///
+    fn make_unmake(board: &mut Board, depth: u8) {
+        for m in board.legal_moves() {
+            let before = *board;
+            let expected = board.make_move(&m).unwrap();
+
+            let undo = board.make(m);
+            assert_eq!(*board, expected, "{} {:?}", before.export_fen(), m);
+
+            if depth > 1 {
+                make_unmake(board, depth - 1);
+            }
+
+            board.unmake(m, undo);
+            assert_eq!(*board, before, "{} {:?}", before.export_fen(), m);
+        }
+    }
+
+    #[test]
+    fn perft_positions() {
+        let positions = [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+            "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+        ];
+
+        for fen in positions {
+            make_unmake(&mut Board::new(fen), 3);
+        }
+    }
+
+    #[test]
+    fn counters_and_rights() {
+        // black castles, which resets the castling rights and increments the fullmove number
+        let mut board = Board::new("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 5 20");
+        let before = board;
+
+        let m = board
+            .legal_moves()
+            .into_iter()
+            .find(|m| matches!(m, Move::Castle { to: Square::G8, .. }))
+            .unwrap();
+        let undo = board.make(m);
+
+        assert_eq!(board.export_fen(), "r4rk1/8/8/8/8/8/8/R3K2R w KQ - 6 21");
+
+        board.unmake(m, undo);
+
+        assert_eq!(board, before);
+        assert_eq!(board.hash(), before.hash());
+    }
+}
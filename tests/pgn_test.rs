@@ -0,0 +1,95 @@
+extern crate osprey;
+
+#[cfg(test)]
+mod pgn_tests {
+    use crate::osprey::pgn::{GameResult, PgnGame, PgnReader, PgnWriter, SEVEN_TAG_ROSTER};
+
+    fn read_games() -> Vec<PgnGame> {
+        PgnReader::open("tests/games.pgn")
+            .unwrap()
+            .collect::<Result<Vec<PgnGame>, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn opera_game() {
+        let games = read_games();
+        assert_eq!(games.len(), 3);
+
+        let game = &games[0];
+
+        for (tag, name) in game.tags.iter().zip(SEVEN_TAG_ROSTER) {
+            assert_eq!(tag.0, name);
+        }
+        assert_eq!(game.tag("White"), Some("Paul Morphy"));
+        assert_eq!(game.result, Some(GameResult::WhiteWins));
+        assert_eq!(game.moves.len(), 33);
+
+        // the last move mates
+        let board = game.board();
+        assert!(board.legal_moves().is_empty());
+        assert_eq!(
+            board.export_fen(),
+            "1n1Rkb1r/p4ppp/4q3/4p1B1/4P3/8/PPP2PPP/2K5 b k - 1 17"
+        );
+
+        // the variation replaces 15... Nxd7 and also ends in mate
+        let variation = &game.moves[29].variations[0];
+        assert_eq!(variation.len(), 4);
+        assert_eq!(game.moves[18].nags, vec![1]);
+        assert_eq!(game.moves[17].nags, vec![6]);
+    }
+
+    #[test]
+    fn fen_game() {
+        let games = read_games();
+        let game = &games[1];
+
+        assert_eq!(
+            game.start.export_fen(),
+            "1K1k4/1P6/8/8/8/8/r7/2R5 w - - 0 1"
+        );
+        assert_eq!(game.moves.len(), 13);
+        assert_eq!(
+            game.board().export_fen(),
+            "8/1P2k3/8/1K6/1R6/8/8/1r6 b - - 13 7"
+        );
+    }
+
+    #[test]
+    fn compact_movetext() {
+        let games = read_games();
+        let game = &games[2];
+
+        assert_eq!(game.moves.len(), 7);
+        assert_eq!(game.moves[5].nags, vec![4]);
+        assert!(game.board().legal_moves().is_empty());
+    }
+
+    #[test]
+    fn write_and_read_back() {
+        for game in read_games() {
+            let mut writer = PgnWriter::new(game.start);
+
+            for (name, value) in &game.tags {
+                writer.tag(name, value);
+            }
+            for m in &game.moves {
+                writer.push(m.m).unwrap();
+            }
+            writer.set_result(game.result.unwrap());
+
+            let pgn = writer.to_string();
+            assert!(pgn.lines().all(|line| line.len() < 80));
+
+            let written = PgnReader::new(pgn.as_bytes()).next().unwrap().unwrap();
+            let moves: Vec<_> = game.moves.iter().map(|m| m.m).collect();
+            let written_moves: Vec<_> = written.moves.iter().map(|m| m.m).collect();
+
+            assert_eq!(written.tags, game.tags);
+            assert_eq!(written.start, game.start);
+            assert_eq!(written_moves, moves);
+            assert_eq!(written.result, game.result);
+        }
+    }
+}
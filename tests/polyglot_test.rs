@@ -0,0 +1,100 @@
+extern crate osprey;
+
+#[cfg(test)]
+mod polyglot_tests {
+    use crate::osprey::polyglot::{BookSelection, PolyglotBook};
+    use crate::osprey::{Board, Move, Square};
+
+    const BOOK: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/book.bin");
+
+    #[test]
+    fn entries() {
+        let mut book = PolyglotBook::open(BOOK).unwrap();
+
+        assert_eq!(book.len(), 8);
+        assert_eq!(book.entries(0x463b96181691fc9c).unwrap().len(), 4);
+        assert_eq!(book.entries(0x1).unwrap().len(), 1);
+        assert_eq!(book.entries(0xffffffffffffffff).unwrap().len(), 1);
+        assert_eq!(book.entries(0x2).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn start_position() {
+        let mut book = PolyglotBook::open(BOOK).unwrap();
+        let board = Board::new("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+
+        // the illegal e2e5 entry is skipped
+        let moves: Vec<Move> = book.moves(&board).unwrap().iter().map(|m| m.m).collect();
+        assert_eq!(
+            moves,
+            vec![
+                Move::Normal {
+                    from: Square::E2,
+                    to: Square::E4
+                },
+                Move::Normal {
+                    from: Square::D2,
+                    to: Square::D4
+                },
+                Move::Normal {
+                    from: Square::G1,
+                    to: Square::F3
+                },
+            ]
+        );
+
+        assert_eq!(
+            book.select_move(&board, BookSelection::Best).unwrap(),
+            Some(Move::Normal {
+                from: Square::E2,
+                to: Square::E4
+            })
+        );
+
+        for selection in [BookSelection::Weighted, BookSelection::Random] {
+            let m = book.select_move(&board, selection).unwrap().unwrap();
+            assert!(moves.contains(&m));
+        }
+
+        // the same random number selects the same move
+        for (random, m) in moves.iter().enumerate() {
+            assert_eq!(
+                book.select_move_with(&board, BookSelection::Random, random as u64)
+                    .unwrap(),
+                Some(*m)
+            );
+        }
+        assert_eq!(
+            book.select_move_with(&board, BookSelection::Weighted, 0)
+                .unwrap(),
+            Some(moves[0])
+        );
+    }
+
+    #[test]
+    fn after_move() {
+        let mut book = PolyglotBook::open(BOOK).unwrap();
+        let board = Board::new("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")
+            .make_move(&Move::Normal {
+                from: Square::E2,
+                to: Square::E4,
+            })
+            .unwrap();
+
+        assert_eq!(
+            book.select_move(&board, BookSelection::Best).unwrap(),
+            Some(Move::Normal {
+                from: Square::C7,
+                to: Square::C5
+            })
+        );
+
+        let board = board
+            .make_move(&Move::Normal {
+                from: Square::C7,
+                to: Square::C5,
+            })
+            .unwrap();
+        assert_eq!(book.select_move(&board, BookSelection::Best).unwrap(), None);
+    }
+}